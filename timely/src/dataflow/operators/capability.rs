@@ -95,6 +95,63 @@ impl<T: Timestamp> Capability<T> {
         let new_cap = self.delayed(new_time);
         *self = new_cap;
     }
+
+    /// Transfers the capability to the output port of `output`, retaining its timestamp.
+    ///
+    /// The capability is released for its current output and acquired for the output of
+    /// `output`, so that the number of outstanding capabilities is unchanged. Progress
+    /// tracking then reflects that downstream of the new output, rather than the old one,
+    /// may still receive messages at the capability's time.
+    ///
+    /// This method panics if `output.time` is not less or equal to `self.time`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::dataflow::InputHandle;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::{Input, Probe};
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    ///
+    /// timely::execute_directly(|worker| {
+    ///
+    ///     let mut input = InputHandle::<u64, ()>::new();
+    ///     let (probe_a, probe_b) = worker.dataflow(|scope| {
+    ///         let stream = scope.input_from(&mut input);
+    ///         let mut builder = OperatorBuilder::new("Reroute".to_owned(), scope.clone());
+    ///         let _input = builder.new_input(&stream, Pipeline);
+    ///         let (_output_a, stream_a) = builder.new_output::<Vec<()>>();
+    ///         let (_output_b, stream_b) = builder.new_output::<Vec<()>>();
+    ///         builder.build(move |mut capabilities| {
+    ///             let cap_b = capabilities.pop().unwrap();
+    ///             let mut cap_a = capabilities.pop().unwrap();
+    ///             cap_a.downgrade(&5);
+    ///             cap_a.transfer(&cap_b);
+    ///             let mut held = Some(cap_a);
+    ///             move |frontiers| {
+    ///                 if frontiers[0].is_empty() { held = None; }
+    ///             }
+    ///         });
+    ///         (stream_a.probe(), stream_b.probe())
+    ///     });
+    ///
+    ///     // The held time now constrains the second output, and not the first.
+    ///     input.advance_to(10);
+    ///     worker.step_while(|| probe_a.less_than(&10));
+    ///     assert!(probe_b.less_equal(&5));
+    ///     assert!(!probe_b.less_than(&5));
+    ///
+    ///     input.close();
+    ///     worker.step_while(|| !probe_b.done());
+    /// });
+    /// ```
+    pub fn transfer(&mut self, output: &Capability<T>) {
+        if !output.time.less_equal(&self.time) {
+            panic!("Attempted to transfer {:?} to the output of {:?}, which is not `less_equal` the capability's time.", self, output);
+        }
+        let new_cap = mint(self.time.clone(), output.internal.clone());
+        *self = new_cap;
+    }
 }
 
 /// Creates a new capability at `t` while incrementing (and keeping a reference to) the provided
@@ -36,3 +36,42 @@ impl<G: Scope, D: Data> Filter<D> for Stream<G, D> {
         })
     }
 }
+
+/// Extension trait for filtering by time.
+pub trait FilterWhen<G: Scope, D: Data> {
+    /// Returns a new instance of `self` containing only records at times satisfying `condition`.
+    ///
+    /// Records at other times are consumed and dropped. The frontier of the returned stream advances
+    /// as its input's does, so times that do not satisfy `condition` complete without any records.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Delay, FilterWhen, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..6).to_stream(scope)
+    ///           .delay(|x,_t| *x) // data 0..6 at time 0..6
+    ///           .filter_when(|time| time % 2 == 0)
+    ///           .capture()
+    /// });
+    ///
+    /// let extracted = captured.extract();
+    /// assert_eq!(extracted, vec![(0, vec![0]), (2, vec![2]), (4, vec![4])]);
+    /// ```
+    fn filter_when(&self, condition: impl Fn(&G::Timestamp)->bool+'static) -> Self;
+}
+
+impl<G: Scope, D: Data> FilterWhen<G, D> for Stream<G, D> {
+    fn filter_when(&self, condition: impl Fn(&G::Timestamp)->bool+'static) -> Stream<G, D> {
+        let mut vector = Vec::new();
+        self.unary(Pipeline, "FilterWhen", move |_,_| move |input, output| {
+            input.for_each(|time, data| {
+                if condition(time.time()) {
+                    data.swap(&mut vector);
+                    output.session(&time).give_vec(&mut vector);
+                }
+            });
+        })
+    }
+}
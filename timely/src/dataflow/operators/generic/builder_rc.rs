@@ -65,6 +65,58 @@ impl<G: Scope> OperatorBuilder<G> {
     }
 
     /// Adds a new input with connection information to a generic operator builder, returning the `Pull` implementor to use.
+    ///
+    /// The connection indicates for each output already added to the builder the summaries by
+    /// which timestamps on the input may advance before emerging at the output. An operator that
+    /// only ever produces outputs at later times than its inputs should declare this, as it allows
+    /// the output frontier to advance beyond the input frontier. For example, an operator which
+    /// delays each record by one can declare a connection of `Antichain::from_elem(1)` for its
+    /// output, whose frontier then trails the input frontier by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::dataflow::InputHandle;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::{Input, Probe};
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    /// use timely::progress::Antichain;
+    ///
+    /// timely::execute_directly(|worker| {
+    ///
+    ///     let mut input = InputHandle::<u64, u64>::new();
+    ///     let probe = worker.dataflow(|scope| {
+    ///
+    ///         let stream = scope.input_from(&mut input);
+    ///
+    ///         let mut builder = OperatorBuilder::new("Delay".to_owned(), scope.clone());
+    ///         let (mut output, delayed) = builder.new_output();
+    ///         let mut input = builder.new_input_connection(&stream, Pipeline, vec![Antichain::from_elem(1)]);
+    ///
+    ///         builder.build(move |_capabilities| {
+    ///             let mut vector = Vec::new();
+    ///             move |_frontiers| {
+    ///                 let mut output = output.activate();
+    ///                 input.for_each(|cap, data| {
+    ///                     data.swap(&mut vector);
+    ///                     let time = cap.time() + 1;
+    ///                     output.session(&cap.delayed(&time)).give_vec(&mut vector);
+    ///                 });
+    ///             }
+    ///         });
+    ///
+    ///         delayed.probe()
+    ///     });
+    ///
+    ///     for round in 0 .. 10 {
+    ///         input.send(round);
+    ///         input.advance_to(round + 1);
+    ///         worker.step_while(|| probe.less_than(&(round + 2)));
+    ///         // The output frontier trails the input frontier by one.
+    ///         assert!(probe.less_equal(&(round + 2)));
+    ///     }
+    /// });
+    /// ```
     pub fn new_input_connection<D: Data, P>(&mut self, stream: &Stream<G, D>, pact: P, connection: Vec<Antichain<<G::Timestamp as Timestamp>::Summary>>) -> InputHandle<G::Timestamp, D, P::Puller>
         where
             P: ParallelizationContract<G::Timestamp, D> {
@@ -85,6 +137,9 @@ impl<G: Scope> OperatorBuilder<G> {
     }
 
     /// Adds a new output with connection information to a generic operator builder, returning the `Pull` implementor to use.
+    ///
+    /// The connection indicates for each input already added to the builder the summaries by which
+    /// timestamps on that input may advance before emerging at the output, as for `new_input_connection`.
     pub fn new_output_connection<D: Data>(&mut self, connection: Vec<Antichain<<G::Timestamp as Timestamp>::Summary>>) -> (OutputWrapper<G::Timestamp, D, Tee<G::Timestamp, D>>, Stream<G, D>) {
 
         let (tee, stream) = self.builder.new_output_connection(connection);
@@ -17,7 +17,8 @@ pub use self::concat::{Concat, Concatenate};
 pub use self::partition::Partition;
 pub use self::map::Map;
 pub use self::inspect::Inspect;
-pub use self::filter::Filter;
+pub use self::filter::{Filter, FilterWhen};
+pub use self::sample::Sample;
 pub use self::delay::Delay;
 pub use self::exchange::Exchange;
 pub use self::broadcast::Broadcast;
@@ -42,6 +43,7 @@ pub mod partition;
 pub mod map;
 pub mod inspect;
 pub mod filter;
+pub mod sample;
 pub mod delay;
 pub mod exchange;
 pub mod broadcast;
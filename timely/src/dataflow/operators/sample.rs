@@ -0,0 +1,52 @@
+//! Samples a stream, forwarding a fixed fraction of its records.
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Extension trait for sampling.
+pub trait Sample<D: Data> {
+    /// Returns a new instance of `self` containing one of every `period` records.
+    ///
+    /// Records are sampled deterministically: each worker forwards the first record it receives
+    /// and every `period`-th record after it, regardless of their times. Records not forwarded
+    /// are consumed and dropped, and the stream's frontier advances as its input's does.
+    ///
+    /// This method panics if `period` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Sample, Capture};
+    /// use timely::dataflow::operators::capture::Extract;
+    ///
+    /// let captured = timely::example(|scope| {
+    ///     (0..11).to_stream(scope)
+    ///            .sample(2)
+    ///            .capture()
+    /// });
+    ///
+    /// let extracted = captured.extract();
+    /// assert_eq!(extracted, vec![(0, vec![0, 2, 4, 6, 8, 10])]);
+    /// ```
+    fn sample(&self, period: usize) -> Self;
+}
+
+impl<G: Scope, D: Data> Sample<D> for Stream<G, D> {
+    fn sample(&self, period: usize) -> Stream<G, D> {
+        assert!(period > 0, "Sampling period must be positive.");
+        let mut vector = Vec::new();
+        let mut offset = 0;
+        self.unary(Pipeline, "Sample", move |_,_| move |input, output| {
+            input.for_each(|time, data| {
+                data.swap(&mut vector);
+                let skip = (period - offset) % period;
+                offset = (offset + vector.len()) % period;
+                let mut sampled = vector.drain(..).skip(skip).step_by(period).collect::<Vec<_>>();
+                if !sampled.is_empty() {
+                    output.session(&time).give_vec(&mut sampled);
+                }
+            });
+        })
+    }
+}
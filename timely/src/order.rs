@@ -47,6 +47,39 @@ macro_rules! implement_total {
 implement_partial!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, (), ::std::time::Duration,);
 implement_total!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, (), ::std::time::Duration,);
 
+/// A partially ordered type in which each pair of elements has a least upper bound and a greatest
+/// lower bound.
+///
+/// # Examples
+///
+/// ```
+/// use timely::order::{Lattice, Product};
+///
+/// let time1 = Product::new(3, 5);
+/// let time2 = Product::new(4, 2);
+/// assert_eq!(time1.join(&time2), Product::new(4, 5));
+/// assert_eq!(time1.meet(&time2), Product::new(3, 2));
+/// ```
+pub trait Lattice : PartialOrder {
+    /// The least element greater or equal to both `self` and `other`.
+    fn join(&self, other: &Self) -> Self;
+    /// The greatest element less or equal to both `self` and `other`.
+    fn meet(&self, other: &Self) -> Self;
+}
+
+macro_rules! implement_lattice {
+    ($($index_type:ty,)*) => (
+        $(
+            impl Lattice for $index_type {
+                #[inline] fn join(&self, other: &Self) -> Self { ::std::cmp::max(*self, *other) }
+                #[inline] fn meet(&self, other: &Self) -> Self { ::std::cmp::min(*self, *other) }
+            }
+        )*
+    )
+}
+
+implement_lattice!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, (), ::std::time::Duration,);
+
 
 use std::fmt::{Formatter, Error, Debug};
 
@@ -79,6 +112,21 @@ pub struct Product<TOuter, TInner> {
 
 impl<TOuter, TInner> Product<TOuter, TInner> {
     /// Creates a new product from outer and inner coordinates.
+    ///
+    /// The coordinates are available by name, as the `outer` and `inner` fields, and the outer
+    /// coordinate is what `Refines::to_outer` returns when leaving a nested scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::order::Product;
+    /// use timely::progress::timestamp::Refines;
+    ///
+    /// let time = Product::new(3u64, 7u32);
+    /// assert_eq!((time.outer, time.inner), (3, 7));
+    /// assert_eq!(Refines::<u64>::to_outer(time), 3);
+    /// assert_eq!(<Product<u64, u32> as Refines<u64>>::to_inner(3), Product::new(3, 0));
+    /// ```
     pub fn new(outer: TOuter, inner: TInner) -> Product<TOuter, TInner> {
         Product {
             outer,
@@ -87,6 +135,35 @@ impl<TOuter, TInner> Product<TOuter, TInner> {
     }
 }
 
+/// Converts a pair of coordinates into a product, to ease migration from tuples.
+///
+/// # Examples
+///
+/// ```
+/// use timely::order::Product;
+/// use timely::progress::PathSummary;
+///
+/// let time: Product<u64, u32> = (3, 7).into();
+/// assert_eq!(time, Product::new(3, 7));
+/// assert_eq!(<(u64, u32)>::from(time), (3, 7));
+///
+/// // Summaries apply to each coordinate independently.
+/// let summary: Product<u64, u32> = (1, 2).into();
+/// assert_eq!(summary.results_in(&time), Some((4, 9).into()));
+/// ```
+impl<TOuter, TInner> From<(TOuter, TInner)> for Product<TOuter, TInner> {
+    fn from((outer, inner): (TOuter, TInner)) -> Self {
+        Product::new(outer, inner)
+    }
+}
+
+/// Converts a product into its pair of coordinates.
+impl<TOuter, TInner> From<Product<TOuter, TInner>> for (TOuter, TInner) {
+    fn from(product: Product<TOuter, TInner>) -> Self {
+        (product.outer, product.inner)
+    }
+}
+
 /// Debug implementation to avoid seeing fully qualified path names.
 impl<TOuter: Debug, TInner: Debug> Debug for Product<TOuter, TInner> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
@@ -101,6 +178,17 @@ impl<TOuter: PartialOrder, TInner: PartialOrder> PartialOrder for Product<TOuter
     }
 }
 
+impl<TOuter: Lattice, TInner: Lattice> Lattice for Product<TOuter, TInner> {
+    #[inline]
+    fn join(&self, other: &Self) -> Self {
+        Product::new(self.outer.join(&other.outer), self.inner.join(&other.inner))
+    }
+    #[inline]
+    fn meet(&self, other: &Self) -> Self {
+        Product::new(self.outer.meet(&other.outer), self.inner.meet(&other.inner))
+    }
+}
+
 impl<TOuter: Timestamp, TInner: Timestamp> Timestamp for Product<TOuter, TInner> {
     type Summary = Product<TOuter::Summary, TInner::Summary>;
 }
@@ -125,6 +213,187 @@ impl<TOuter: Timestamp, TInner: Timestamp> PathSummary<Product<TOuter, TInner>>
     }
 }
 
+/// A triple of timestamps, for scopes nested three deep.
+///
+/// `Product3<T1, T2, T3>` is ordered as `Product<Product<T1, T2>, T3>` is, by the product order
+/// across all coordinates, and refines `Product<T1, T2>` by adding an innermost coordinate. This
+/// allows a scope nested within an iterative scope to use flat timestamps and summaries, rather
+/// than nested products, through `Scope::scoped`.
+///
+/// # Examples
+///
+/// ```
+/// use timely::order::{PartialOrder, Product, Product3};
+/// use timely::progress::timestamp::Refines;
+///
+/// let time = Product3::new(0u64, 5u64, 0u32);
+/// assert!(!time.less_equal(&Product3::new(1, 0, 0)));
+/// assert!(!Product3::new(1, 0, 0).less_equal(&time));
+/// assert_eq!(Refines::<Product<u64, u64>>::to_outer(time), Product::new(0, 5));
+/// ```
+#[derive(Abomonation, Copy, Clone, Hash, Eq, PartialEq, Default, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct Product3<T1, T2, T3> {
+    /// Outermost timestamp.
+    pub outer: T1,
+    /// Middle timestamp.
+    pub middle: T2,
+    /// Innermost timestamp.
+    pub inner: T3,
+}
+
+impl<T1, T2, T3> Product3<T1, T2, T3> {
+    /// Creates a new triple from its coordinates, outermost first.
+    pub fn new(outer: T1, middle: T2, inner: T3) -> Product3<T1, T2, T3> {
+        Product3 {
+            outer,
+            middle,
+            inner,
+        }
+    }
+}
+
+/// Debug implementation to avoid seeing fully qualified path names.
+impl<T1: Debug, T2: Debug, T3: Debug> Debug for Product3<T1, T2, T3> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.write_str(&format!("({:?}, {:?}, {:?})", self.outer, self.middle, self.inner))
+    }
+}
+
+impl<T1: PartialOrder, T2: PartialOrder, T3: PartialOrder> PartialOrder for Product3<T1, T2, T3> {
+    #[inline]
+    fn less_equal(&self, other: &Self) -> bool {
+        self.outer.less_equal(&other.outer) && self.middle.less_equal(&other.middle) && self.inner.less_equal(&other.inner)
+    }
+}
+
+impl<T1: Timestamp, T2: Timestamp, T3: Timestamp> Timestamp for Product3<T1, T2, T3> {
+    type Summary = Product3<T1::Summary, T2::Summary, T3::Summary>;
+}
+
+impl<T1: Timestamp, T2: Timestamp, T3: Timestamp> PathSummary<Product3<T1, T2, T3>> for Product3<T1::Summary, T2::Summary, T3::Summary> {
+    #[inline]
+    fn results_in(&self, product: &Product3<T1, T2, T3>) -> Option<Product3<T1, T2, T3>> {
+        let outer = self.outer.results_in(&product.outer)?;
+        let middle = self.middle.results_in(&product.middle)?;
+        let inner = self.inner.results_in(&product.inner)?;
+        Some(Product3::new(outer, middle, inner))
+    }
+    #[inline]
+    fn followed_by(&self, other: &Product3<T1::Summary, T2::Summary, T3::Summary>) -> Option<Product3<T1::Summary, T2::Summary, T3::Summary>> {
+        let outer = self.outer.followed_by(&other.outer)?;
+        let middle = self.middle.followed_by(&other.middle)?;
+        let inner = self.inner.followed_by(&other.inner)?;
+        Some(Product3::new(outer, middle, inner))
+    }
+}
+
+impl<T1: Timestamp, T2: Timestamp, T3: Timestamp> Refines<Product<T1, T2>> for Product3<T1, T2, T3> {
+    fn to_inner(other: Product<T1, T2>) -> Self {
+        Product3::new(other.outer, other.inner, Default::default())
+    }
+    fn to_outer(self: Product3<T1, T2, T3>) -> Product<T1, T2> {
+        Product::new(self.outer, self.middle)
+    }
+    fn summarize(path: <Self as Timestamp>::Summary) -> <Product<T1, T2> as Timestamp>::Summary {
+        Product::new(path.outer, path.middle)
+    }
+}
+
+/// A timestamp with a number of coordinates determined at runtime.
+///
+/// Coordinates are ordered by the product order, and coordinates beyond the end of the vector
+/// are zero, so that timestamps of different depths can be compared and the empty timestamp is
+/// the least. Trailing zero coordinates are removed on construction, so that equal timestamps
+/// have equal representations. The timestamp serves as its own path summary, which advances each
+/// coordinate by the corresponding coordinate of the summary and leaves the others untouched.
+///
+/// # Examples
+///
+/// ```
+/// use timely::order::{PartialOrder, VecTimestamp};
+/// use timely::progress::PathSummary;
+///
+/// // Three levels of nesting, advancing the innermost loop.
+/// let time = VecTimestamp::new(vec![2, 0, 4]);
+/// let summary = VecTimestamp::increment(2);
+/// assert_eq!(summary.results_in(&time), Some(VecTimestamp::new(vec![2, 0, 5])));
+/// assert!(!time.less_equal(&VecTimestamp::new(vec![3, 0, 0])));
+///
+/// // Five levels of nesting, composing increments of one coordinate.
+/// let time = VecTimestamp::new(vec![1, 1, 1, 1, 1]);
+/// let twice = summary.followed_by(&summary).unwrap();
+/// assert_eq!(twice, VecTimestamp::new(vec![0, 0, 2]));
+/// assert_eq!(twice.results_in(&time), Some(VecTimestamp::new(vec![1, 1, 3, 1, 1])));
+/// assert!(time.less_equal(&VecTimestamp::new(vec![1, 1, 3, 1, 1])));
+/// assert_eq!(VecTimestamp::new(vec![1, 0, 0]), VecTimestamp::new(vec![1]));
+/// ```
+#[derive(Abomonation, Clone, Hash, Eq, PartialEq, Default, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct VecTimestamp {
+    coordinates: Vec<usize>,
+}
+
+impl VecTimestamp {
+    /// Creates a new timestamp from its coordinates, outermost first.
+    pub fn new(mut coordinates: Vec<usize>) -> VecTimestamp {
+        while coordinates.last() == Some(&0) {
+            coordinates.pop();
+        }
+        VecTimestamp { coordinates }
+    }
+    /// The summary advancing `coordinate` by one, as the feedback edge of a loop at that depth does.
+    pub fn increment(coordinate: usize) -> VecTimestamp {
+        let mut coordinates = vec![0; coordinate + 1];
+        coordinates[coordinate] = 1;
+        VecTimestamp { coordinates }
+    }
+    /// The value of `coordinate`, which is zero beyond the end of the coordinates.
+    pub fn coordinate(&self, coordinate: usize) -> usize {
+        self.coordinates.get(coordinate).cloned().unwrap_or(0)
+    }
+    /// The coordinates, without trailing zeros.
+    pub fn coordinates(&self) -> &[usize] {
+        &self.coordinates[..]
+    }
+    /// Adds the coordinates of `self` and `other`, or returns `None` on overflow.
+    fn checked_add(&self, other: &VecTimestamp) -> Option<VecTimestamp> {
+        let depth = ::std::cmp::max(self.coordinates.len(), other.coordinates.len());
+        let coordinates = (0 .. depth)
+            .map(|index| self.coordinate(index).checked_add(other.coordinate(index)))
+            .collect::<Option<Vec<_>>>()?;
+        Some(VecTimestamp::new(coordinates))
+    }
+}
+
+/// Debug implementation to present the coordinates as a list.
+impl Debug for VecTimestamp {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        f.debug_list().entries(self.coordinates.iter()).finish()
+    }
+}
+
+impl PartialOrder for VecTimestamp {
+    #[inline]
+    fn less_equal(&self, other: &Self) -> bool {
+        self.coordinates.len() <= other.coordinates.len() &&
+        self.coordinates.iter().zip(other.coordinates.iter()).all(|(x, y)| x <= y)
+    }
+}
+
+impl Timestamp for VecTimestamp {
+    type Summary = VecTimestamp;
+}
+
+impl PathSummary<VecTimestamp> for VecTimestamp {
+    #[inline]
+    fn results_in(&self, time: &VecTimestamp) -> Option<VecTimestamp> {
+        self.checked_add(time)
+    }
+    #[inline]
+    fn followed_by(&self, other: &VecTimestamp) -> Option<VecTimestamp> {
+        self.checked_add(other)
+    }
+}
+
 /// A type that does not affect total orderedness.
 ///
 /// This trait is not useful, but must be made public and documented or else Rust
@@ -136,3 +405,6 @@ impl Empty for () { }
 impl<T1: Empty, T2: Empty> Empty for Product<T1, T2> { }
 
 impl<T1, T2> TotalOrder for Product<T1, T2> where T1: Empty, T2: TotalOrder { }
+
+impl<T1: Empty, T2: Empty, T3: Empty> Empty for Product3<T1, T2, T3> { }
+impl<T1, T2, T3> TotalOrder for Product3<T1, T2, T3> where T1: Empty, T2: Empty, T3: TotalOrder { }
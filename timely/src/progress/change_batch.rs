@@ -1,5 +1,7 @@
 //! A collection of updates of the form `(T, i64)`.
 
+use crate::order::PartialOrder;
+
 /// A collection of updates of the form `(T, i64)`.
 ///
 /// A `ChangeBatch` accumulates updates of the form `(T, i64)`, where it is capable of consolidating
@@ -132,6 +134,36 @@ impl<T:Ord> ChangeBatch<T> {
         self.updates.iter()
     }
 
+    /// Iterates over the minimal times with positive accumulated count.
+    ///
+    /// The times are yielded lazily, without allocating a frontier, by comparing each positive
+    /// time against the others. This is quadratic in the number of distinct times, and is meant
+    /// for batches that hold few times; prefer a `MutableAntichain` to maintain larger frontiers.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::ChangeBatch;
+    ///
+    /// let mut batch = ChangeBatch::<usize>::new();
+    /// batch.update(3, 1);
+    /// batch.update(1, 1);
+    /// batch.update(2, 1);
+    /// batch.update(0, -1);
+    /// assert_eq!(batch.frontier_iter().collect::<Vec<_>>(), vec![&1]);
+    ///```
+    pub fn frontier_iter(&mut self) -> impl Iterator<Item=&T> where T: PartialOrder {
+        self.compact();
+        let updates = &self.updates;
+        updates
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .filter(move |(time, _)| {
+                !updates.iter().any(|(other, count)| *count > 0 && other != time && other.less_equal(time))
+            })
+            .map(|(time, _)| time)
+    }
+
     /// Drains the set of updates.
     ///
     /// This operation first compacts the set of updates so that the drained results
@@ -258,6 +290,32 @@ impl<T:Ord> ChangeBatch<T> {
         self.clean = self.updates.len();
     }
 
+    /// Shrinks the capacity of the internal representation, keeping at least `capacity`.
+    ///
+    /// The updates are compacted first, and the capacity is only reduced if they fit within
+    /// `capacity`. This allows a batch that has absorbed a burst of updates to release the
+    /// memory, while retaining enough for typical use.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::ChangeBatch;
+    ///
+    /// let mut batch = ChangeBatch::<usize>::new();
+    /// for time in 0 .. 1000 { batch.update(time, 1); }
+    /// batch.clear();
+    /// batch.shrink_to(16);
+    /// assert!(batch.unstable_internal_updates().capacity() < 1000);
+    ///```
+    pub fn shrink_to(&mut self, capacity: usize) {
+        self.compact();
+        if self.updates.len() <= capacity && self.updates.capacity() > capacity {
+            let mut updates = Vec::with_capacity(capacity);
+            updates.append(&mut self.updates);
+            self.updates = updates;
+        }
+    }
+
     /// Expose the internal vector of updates.
     pub fn unstable_internal_updates(&self) -> &Vec<(T, i64)> { &self.updates }
 
@@ -273,3 +331,101 @@ impl<T:Ord> ChangeBatch<T> {
         }
     }
 }
+
+/// A `ChangeBatch` that reports selected times even when their accumulated count is zero.
+///
+/// Pinning a time allows one to observe its count as it crosses zero, which a `ChangeBatch`
+/// would otherwise discard on compaction. This is meant for debugging, and the pinned times
+/// are kept apart from the batch, which is otherwise unaffected by them.
+///
+/// # Examples
+///
+///```
+/// use timely::progress::change_batch::PinnedChangeBatch;
+///
+/// let mut batch = PinnedChangeBatch::<usize>::new();
+/// batch.pin(3);
+/// batch.update(3, 1);
+/// batch.update(5, 1);
+/// batch.update(3, -1);
+/// assert_eq!(batch.iter().collect::<Vec<_>>(), vec![(&3, 0), (&5, 1)]);
+///
+/// batch.unpin(3);
+/// assert_eq!(batch.iter().collect::<Vec<_>>(), vec![(&5, 1)]);
+///```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PinnedChangeBatch<T> {
+    // The accumulated updates.
+    batch: ChangeBatch<T>,
+    // Sorted times reported even when their accumulation is zero.
+    pinned: Vec<T>,
+}
+
+impl<T:Ord> PinnedChangeBatch<T> {
+
+    /// Allocates a new empty `PinnedChangeBatch` with no pinned times.
+    pub fn new() -> Self {
+        PinnedChangeBatch {
+            batch: ChangeBatch::new(),
+            pinned: Vec::new(),
+        }
+    }
+
+    /// Reports `time` by `iter` and `drain` even when its accumulated count is zero.
+    pub fn pin(&mut self, time: T) {
+        if let Err(position) = self.pinned.binary_search(&time) {
+            self.pinned.insert(position, time);
+        }
+    }
+
+    /// Stops reporting `time` once its accumulated count is zero.
+    pub fn unpin(&mut self, time: T) {
+        if let Ok(position) = self.pinned.binary_search(&time) {
+            self.pinned.remove(position);
+        }
+    }
+
+    /// Adds a new update, for `item` with `value`.
+    #[inline]
+    pub fn update(&mut self, item: T, value: i64) {
+        self.batch.update(item, value);
+    }
+
+    /// Iterates over the accumulated counts, in order of time, including pinned times.
+    pub fn iter(&mut self) -> impl Iterator<Item=(&T, i64)> {
+        self.batch.compact();
+        let mut updates = self.batch.updates.iter().peekable();
+        let mut pinned = self.pinned.iter().peekable();
+        ::std::iter::from_fn(move || {
+            let take_update = match (updates.peek(), pinned.peek()) {
+                (Some((time, _)), Some(pin)) => time <= *pin,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return None,
+            };
+            if take_update {
+                let (time, count) = updates.next().unwrap();
+                if pinned.peek() == Some(&time) { pinned.next(); }
+                Some((time, *count))
+            }
+            else {
+                pinned.next().map(|time| (time, 0))
+            }
+        })
+    }
+
+    /// Drains the accumulated counts, in order of time, including pinned times.
+    ///
+    /// Pinned times remain pinned.
+    pub fn drain(&mut self) -> ::std::vec::IntoIter<(T, i64)> where T: Clone {
+        let drained = self.iter().map(|(time, count)| (time.clone(), count)).collect::<Vec<_>>();
+        self.batch.clear();
+        drained.into_iter()
+    }
+}
+
+impl<T:Ord> Default for PinnedChangeBatch<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,213 @@
+//! An operator wrapper that delays the progress its operator reports.
+//!
+//! This wrapper is meant for testing how drivers and scopes respond to operators that are
+//! slow to report their progress. The wrapped operator runs as normal, but each batch of
+//! progress statements it produces is withheld from the parent scope for a fixed number of
+//! schedulings before being released.
+
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::scheduling::{Schedule, Activator};
+use crate::progress::{Timestamp, Operate, Antichain, ChangeBatch};
+use crate::progress::operate::SharedProgress;
+
+/// Wraps an operator, deferring its reported progress by a number of schedulings.
+///
+/// The progress statements reported by the wrapped operator in one scheduling (consumed
+/// messages, internal capability changes, and produced messages) are released together,
+/// so that the parent scope always observes a consistent if stale view of the operator.
+/// Frontier changes from the parent are passed to the wrapped operator without delay.
+///
+/// Initial capabilities reported by `get_internal_summary` are not delayed, as the parent
+/// scope requires them to initialize its progress tracking.
+pub struct Delayed<T: Timestamp> {
+    operator: Box<dyn Operate<T>>,
+    ticks: usize,
+    activator: Activator,
+    // progress information shared with the wrapped operator.
+    inner: Rc<RefCell<SharedProgress<T>>>,
+    // progress information shared with the parent scope.
+    outer: Rc<RefCell<SharedProgress<T>>>,
+    // withheld progress statements, one entry per scheduling.
+    pending: VecDeque<SharedProgress<T>>,
+}
+
+impl<T: Timestamp> Delayed<T> {
+    /// Wraps `operator` so that its progress is reported `ticks` schedulings late.
+    ///
+    /// The `activator` should activate the wrapped operator's address, and is used to
+    /// ensure the wrapper is rescheduled while it withholds progress statements.
+    pub fn new(operator: Box<dyn Operate<T>>, ticks: usize, activator: Activator) -> Self {
+        let inputs = operator.inputs();
+        let outputs = operator.outputs();
+        Delayed {
+            operator,
+            ticks,
+            activator,
+            inner: Rc::new(RefCell::new(SharedProgress::new(inputs, outputs))),
+            outer: Rc::new(RefCell::new(SharedProgress::new(inputs, outputs))),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Moves frontier changes from the parent scope to the wrapped operator.
+    fn forward_frontiers(&mut self) {
+        let outer = &mut *self.outer.borrow_mut();
+        let inner = &mut *self.inner.borrow_mut();
+        drain_batches(&mut outer.frontiers, &mut inner.frontiers);
+    }
+
+    /// True if any withheld progress statements remain.
+    fn withholding(&mut self) -> bool {
+        self.pending.iter_mut().any(|progress| {
+            progress.consumeds.iter_mut().any(|x| !x.is_empty()) ||
+            progress.internals.iter_mut().any(|x| !x.is_empty()) ||
+            progress.produceds.iter_mut().any(|x| !x.is_empty())
+        })
+    }
+}
+
+/// Drains each of `source` into the corresponding element of `target`.
+fn drain_batches<T: Ord+Clone>(source: &mut [ChangeBatch<T>], target: &mut [ChangeBatch<T>]) {
+    for (source, target) in source.iter_mut().zip(target.iter_mut()) {
+        source.drain_into(target);
+    }
+}
+
+impl<T: Timestamp> Schedule for Delayed<T> {
+    fn name(&self) -> &str { self.operator.name() }
+    fn path(&self) -> &[usize] { self.operator.path() }
+    fn schedule(&mut self) -> bool {
+
+        self.forward_frontiers();
+        let incomplete = self.operator.schedule();
+
+        // Withhold the progress statements of this scheduling.
+        let mut batch = SharedProgress::new(self.operator.inputs(), self.operator.outputs());
+        {
+            let inner = &mut *self.inner.borrow_mut();
+            drain_batches(&mut inner.consumeds, &mut batch.consumeds);
+            drain_batches(&mut inner.internals, &mut batch.internals);
+            drain_batches(&mut inner.produceds, &mut batch.produceds);
+        }
+        self.pending.push_back(batch);
+
+        // Release progress statements that have been withheld long enough.
+        while self.pending.len() > self.ticks {
+            let mut batch = self.pending.pop_front().expect("pending batch must exist");
+            let outer = &mut *self.outer.borrow_mut();
+            drain_batches(&mut batch.consumeds, &mut outer.consumeds);
+            drain_batches(&mut batch.internals, &mut outer.internals);
+            drain_batches(&mut batch.produceds, &mut outer.produceds);
+        }
+
+        let withholding = self.withholding();
+        if withholding {
+            self.activator.activate();
+        }
+
+        incomplete || withholding
+    }
+}
+
+impl<T: Timestamp> Operate<T> for Delayed<T> {
+
+    fn local(&self) -> bool { self.operator.local() }
+    fn inputs(&self) -> usize { self.operator.inputs() }
+    fn outputs(&self) -> usize { self.operator.outputs() }
+
+    fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<T::Summary>>>, Rc<RefCell<SharedProgress<T>>>) {
+        let (summary, inner) = self.operator.get_internal_summary();
+        self.inner = inner;
+        {   // Initial capabilities must be presented immediately.
+            let inner = &mut *self.inner.borrow_mut();
+            let outer = &mut *self.outer.borrow_mut();
+            drain_batches(&mut inner.consumeds, &mut outer.consumeds);
+            drain_batches(&mut inner.internals, &mut outer.internals);
+            drain_batches(&mut inner.produceds, &mut outer.produceds);
+        }
+        (summary, self.outer.clone())
+    }
+
+    fn set_external_summary(&mut self) {
+        self.forward_frontiers();
+        self.operator.set_external_summary();
+    }
+
+    fn notify_me(&self) -> bool { self.operator.notify_me() }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::rc::Rc;
+    use std::cell::RefCell;
+    use std::time::Instant;
+
+    use crate::scheduling::{Schedule, Activations, Activator};
+    use crate::progress::{Operate, Antichain};
+    use crate::progress::operate::SharedProgress;
+
+    use super::Delayed;
+
+    /// An operator with one output that advances its capability by one each scheduling.
+    struct Counter {
+        time: usize,
+        shared: Rc<RefCell<SharedProgress<usize>>>,
+    }
+
+    impl Schedule for Counter {
+        fn name(&self) -> &str { "Counter" }
+        fn path(&self) -> &[usize] { &[0] }
+        fn schedule(&mut self) -> bool {
+            let mut shared = self.shared.borrow_mut();
+            shared.internals[0].update(self.time, -1);
+            self.time += 1;
+            shared.internals[0].update(self.time, 1);
+            true
+        }
+    }
+
+    impl Operate<usize> for Counter {
+        fn inputs(&self) -> usize { 0 }
+        fn outputs(&self) -> usize { 1 }
+        fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<usize>>>, Rc<RefCell<SharedProgress<usize>>>) {
+            self.shared.borrow_mut().internals[0].update(0, 1);
+            (Vec::new(), self.shared.clone())
+        }
+    }
+
+    #[test]
+    fn delayed_capabilities() {
+
+        let activations = Rc::new(RefCell::new(Activations::new(Instant::now())));
+        let counter = Counter { time: 0, shared: Rc::new(RefCell::new(SharedProgress::new(0, 1))) };
+
+        let ticks = 3;
+        let mut delayed = Delayed::new(Box::new(counter), ticks, Activator::new(&[0], activations));
+        let (_summary, shared) = delayed.get_internal_summary();
+
+        // The initial capability is reported immediately.
+        let mut frontier = 0;
+        let mut initial = shared.borrow_mut().internals[0].drain().collect::<Vec<_>>();
+        initial.sort();
+        assert_eq!(initial, vec![(0, 1)]);
+
+        for tick in 0 .. 10 {
+            delayed.schedule();
+            let mut changes = shared.borrow_mut().internals[0].drain().collect::<Vec<_>>();
+            changes.sort();
+            if tick >= ticks {
+                // The operator advanced to `tick + 1`; we should see its advance `ticks` ago.
+                assert_eq!(changes, vec![(frontier, -1), (frontier + 1, 1)]);
+                frontier += 1;
+                assert_eq!(frontier + ticks, tick + 1);
+            }
+            else {
+                assert!(changes.is_empty());
+            }
+        }
+    }
+}
@@ -1,7 +1,91 @@
 //! Tracks minimal sets of mutually incomparable elements of a partial order.
 
+use std::marker::PhantomData;
+
 use crate::progress::ChangeBatch;
-use crate::order::PartialOrder;
+use crate::order::{Lattice, PartialOrder};
+
+/// A test of whether one element renders another redundant in an antichain.
+///
+/// An `Antichain` retains only elements that are not greater or equal to other elements. By default
+/// this is determined by `PartialOrder::less_equal`, but timestamp types with a cheaper test of the
+/// same relation, for example a comparison of bitmasks, may supply it through a custom reducer.
+/// The test must agree with `PartialOrder::less_equal` wherever both are defined, as the other
+/// methods of an antichain may rely on either.
+///
+/// # Examples
+///
+///```
+/// use timely::progress::frontier::{Antichain, Reducer};
+///
+/// // Sets of up to 64 flags, ordered by inclusion.
+/// #[derive(Clone, Debug, Eq, PartialEq)]
+/// struct Flags(Vec<bool>);
+///
+/// impl timely::order::PartialOrder for Flags {
+///     fn less_equal(&self, other: &Self) -> bool {
+///         self.0.iter().zip(other.0.iter()).all(|(a, b)| !a || *b)
+///     }
+/// }
+///
+/// // The same relation as the partial order, tested through its definition.
+/// #[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// struct Inclusion;
+/// impl Reducer<Flags> for Inclusion {
+///     fn dominates(a: &Flags, b: &Flags) -> bool {
+///         a.0.iter().zip(b.0.iter()).all(|(a, b)| !a || *b)
+///     }
+/// }
+///
+/// // The same relation, tested on bitmasks.
+/// #[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// struct Bitmask;
+/// impl Reducer<Flags> for Bitmask {
+///     fn dominates(a: &Flags, b: &Flags) -> bool {
+///         let mask = |f: &Flags| f.0.iter().enumerate().fold(0u64, |m, (i, x)| m | ((*x as u64) << i));
+///         mask(a) & !mask(b) == 0
+///     }
+/// }
+///
+/// let elements = vec![
+///     Flags(vec![true, false, true]),
+///     Flags(vec![false, true, true]),
+///     Flags(vec![true, false, false]),
+///     Flags(vec![true, true, false]),
+/// ];
+///
+/// let mut default = Antichain::new();
+/// let mut inclusion = Antichain::<_, Inclusion>::with_reducer();
+/// let mut bitmask = Antichain::<_, Bitmask>::with_reducer();
+/// for element in elements {
+///     let inserted = default.insert(element.clone());
+///     assert_eq!(inserted, inclusion.insert(element.clone()));
+///     assert_eq!(inserted, bitmask.insert(element));
+/// }
+/// assert_eq!(default.elements(), inclusion.elements());
+/// assert_eq!(default.elements(), bitmask.elements());
+/// assert_eq!(default.elements(), &[Flags(vec![false, true, true]), Flags(vec![true, false, false])]);
+///```
+pub trait Reducer<T> {
+    /// Returns true if `a` is less or equal to `b`, so that `b` is redundant in the presence of `a`.
+    fn dominates(a: &T, b: &T) -> bool;
+    /// Returns true if `a` is strictly less than `b`.
+    #[inline]
+    fn strictly_dominates(a: &T, b: &T) -> bool where T: PartialEq {
+        Self::dominates(a, b) && a != b
+    }
+}
+
+/// The default reducer, which uses `PartialOrder::less_equal`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PartialOrderReducer;
+
+impl<T: PartialOrder> Reducer<T> for PartialOrderReducer {
+    #[inline]
+    fn dominates(a: &T, b: &T) -> bool { a.less_equal(b) }
+    #[inline]
+    fn strictly_dominates(a: &T, b: &T) -> bool { a.less_than(b) }
+}
 
 /// A set of mutually incomparable elements.
 ///
@@ -9,12 +93,39 @@ use crate::order::PartialOrder;
 /// This antichain implementation allows you to repeatedly introduce elements to the antichain, and
 /// which will evict larger elements to maintain the *minimal* antichain, those incomparable elements
 /// no greater than any other element.
+///
+/// Elements are compared using a `Reducer`, which by default is the partial order on the elements.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub struct Antichain<T> {
-    elements: Vec<T>
+pub struct Antichain<T, R = PartialOrderReducer> {
+    elements: Vec<T>,
+    reducer: PhantomData<R>,
 }
 
 impl<T: PartialOrder> Antichain<T> {
+    /// Creates a new empty `Antichain`.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::Antichain;
+    ///
+    /// let mut frontier = Antichain::<u32>::new();
+    ///```
+    pub fn new() -> Antichain<T> { Antichain::with_reducer() }
+
+    /// Creates a new singleton `Antichain`.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::Antichain;
+    ///
+    /// let mut frontier = Antichain::from_elem(2);
+    ///```
+    pub fn from_elem(element: T) -> Antichain<T> { Antichain { elements: vec![element], reducer: PhantomData } }
+}
+
+impl<T: PartialOrder, R: Reducer<T>> Antichain<T, R> {
     /// Updates the `Antichain` if the element is not greater than or equal to some present element.
     ///
     /// Returns true if element is added to the set
@@ -29,13 +140,48 @@ impl<T: PartialOrder> Antichain<T> {
     /// assert!(!frontier.insert(3));
     ///```
     pub fn insert(&mut self, element: T) -> bool {
-        if !self.elements.iter().any(|x| x.less_equal(&element)) {
-            self.elements.retain(|x| !element.less_equal(x));
+        self.insert_reporting(element).0
+    }
+
+    /// Inserts as `insert` does, and also returns the elements the insertion evicts.
+    ///
+    /// The evicted elements are those greater or equal to `element`, which are listed in no
+    /// particular order; none are evicted if `element` is not inserted. The remaining elements
+    /// keep their order.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::Antichain;
+    /// use timely::order::Product;
+    ///
+    /// let mut frontier = Antichain::new();
+    /// frontier.extend(vec![Product::new(1, 7), Product::new(3, 5), Product::new(5, 3), Product::new(7, 1)]);
+    ///
+    /// let (inserted, mut evicted) = frontier.insert_reporting(Product::new(2, 2));
+    /// evicted.sort();
+    /// assert!(inserted);
+    /// assert_eq!(evicted, vec![Product::new(3, 5), Product::new(5, 3)]);
+    /// assert_eq!(frontier.elements(), &[Product::new(1, 7), Product::new(7, 1), Product::new(2, 2)]);
+    ///
+    /// assert_eq!(frontier.insert_reporting(Product::new(2, 3)), (false, vec![]));
+    ///```
+    pub fn insert_reporting(&mut self, element: T) -> (bool, Vec<T>) {
+        if !self.elements.iter().any(|x| R::dominates(x, &element)) {
+            // Move retained elements to the front, in order, and split off the evicted elements.
+            let mut retained = 0;
+            for index in 0 .. self.elements.len() {
+                if !R::dominates(&element, &self.elements[index]) {
+                    self.elements.swap(retained, index);
+                    retained += 1;
+                }
+            }
+            let evicted = self.elements.split_off(retained);
             self.elements.push(element);
-            true
+            (true, evicted)
         }
         else {
-            false
+            (false, Vec::new())
         }
     }
 
@@ -59,27 +205,61 @@ impl<T: PartialOrder> Antichain<T> {
         added
     }
 
-    /// Creates a new empty `Antichain`.
+    /// Removes `time` from the antichain, and returns true if it was present.
+    ///
+    /// Removing an element leaves the others mutually incomparable, so the antichain remains minimal,
+    /// though elements the removed element once evicted are not restored. This removes an element
+    /// outright, unlike `MutableAntichain`, whose elements are present while their counts are positive.
     ///
     /// # Examples
     ///
     ///```
     /// use timely::progress::frontier::Antichain;
+    /// use timely::order::Product;
     ///
-    /// let mut frontier = Antichain::<u32>::new();
+    /// let mut frontier = Antichain::new();
+    /// frontier.extend(vec![Product::new(2, 3), Product::new(3, 2)]);
+    /// assert!(frontier.remove(&Product::new(2, 3)));
+    /// assert!(!frontier.remove(&Product::new(2, 3)));
+    /// assert_eq!(frontier.elements(), &[Product::new(3, 2)]);
     ///```
-    pub fn new() -> Antichain<T> { Antichain { elements: Vec::new() } }
+    pub fn remove(&mut self, time: &T) -> bool where T: PartialEq {
+        if let Some(position) = self.elements.iter().position(|x| x == time) {
+            self.elements.remove(position);
+            true
+        }
+        else {
+            false
+        }
+    }
 
-    /// Creates a new singleton `Antichain`.
+    /// Splits the antichain into the elements less or equal to `pivot`, and the others.
+    ///
+    /// For partially ordered elements the second antichain contains both the elements greater than
+    /// `pivot` and those incomparable to it. Each part is a subset of an antichain, and so is itself
+    /// an antichain. For totally ordered elements an antichain has at most one element, which lands
+    /// in one part or the other.
     ///
     /// # Examples
     ///
     ///```
     /// use timely::progress::frontier::Antichain;
+    /// use timely::order::Product;
     ///
-    /// let mut frontier = Antichain::from_elem(2);
+    /// let mut frontier = Antichain::new();
+    /// frontier.extend(vec![Product::new(1, 9), Product::new(5, 5), Product::new(9, 1)]);
+    ///
+    /// let (lower, upper) = frontier.partition(&Product::new(5, 6));
+    /// assert_eq!(lower.elements(), &[Product::new(5, 5)]);
+    /// assert_eq!(upper.elements(), &[Product::new(1, 9), Product::new(9, 1)]);
     ///```
-    pub fn from_elem(element: T) -> Antichain<T> { Antichain { elements: vec![element] } }
+    pub fn partition(&self, pivot: &T) -> (Antichain<T, R>, Antichain<T, R>) where T: Clone {
+        let (lower, upper): (Vec<T>, Vec<T>) = self.elements.iter().cloned().partition(|x| R::dominates(x, pivot));
+        (Antichain { elements: lower, reducer: PhantomData }, Antichain { elements: upper, reducer: PhantomData })
+    }
+
+    /// Creates a new empty `Antichain` whose elements are compared using the reducer `R`.
+    pub fn with_reducer() -> Antichain<T, R> { Antichain { elements: Vec::new(), reducer: PhantomData } }
 
     /// Clears the contents of the antichain.
     ///
@@ -114,7 +294,7 @@ impl<T: PartialOrder> Antichain<T> {
     ///```
     #[inline]
     pub fn less_than(&self, time: &T) -> bool {
-        self.elements.iter().any(|x| x.less_than(time))
+        self.elements.iter().any(|x| R::strictly_dominates(x, time))
     }
 
     /// Returns true if any item in the antichain is less than or equal to the argument.
@@ -134,13 +314,143 @@ impl<T: PartialOrder> Antichain<T> {
     ///```
     #[inline]
     pub fn less_equal(&self, time: &T) -> bool {
-        self.elements.iter().any(|x| x.less_equal(time))
+        self.elements.iter().any(|x| R::dominates(x, time))
+    }
+
+    /// Returns true if `time` is an element of the antichain.
+    ///
+    /// This tests membership rather than comparison: an element greater than some element of the
+    /// antichain is not contained, though the antichain is `less_equal` to it.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::Antichain;
+    /// use timely::order::Product;
+    ///
+    /// let mut frontier = Antichain::new();
+    /// frontier.extend(vec![Product::new(2, 3), Product::new(3, 2)]);
+    /// assert!(frontier.contains(&Product::new(3, 2)));
+    /// assert!(!frontier.contains(&Product::new(3, 3)));
+    /// assert!(frontier.less_equal(&Product::new(3, 3)));
+    /// assert!(!frontier.less_equal(&Product::new(1, 4)));
+    /// assert!(!frontier.less_than(&Product::new(2, 3)));
+    ///```
+    #[inline]
+    pub fn contains(&self, time: &T) -> bool where T: PartialEq {
+        self.elements.iter().any(|x| x == time)
+    }
+
+    /// Iterates over the elements of the antichain, in no particular order.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::Antichain;
+    /// use timely::order::Product;
+    ///
+    /// let mut frontier = Antichain::new();
+    /// frontier.extend(vec![Product::new(1, 4), Product::new(4, 1), Product::new(5, 5)]);
+    /// let mut elements = frontier.iter().cloned().collect::<Vec<_>>();
+    /// elements.sort();
+    /// assert_eq!(elements, vec![Product::new(1, 4), Product::new(4, 1)]);
+    ///```
+    #[inline]
+    pub fn iter(&self) -> ::std::slice::Iter<'_, T> {
+        self.elements.iter()
+    }
+
+    /// The greatest lower bound of two antichains, viewed as frontiers.
+    ///
+    /// The result is the frontier of the times at or beyond either `self` or `other`, whose elements
+    /// are the minimal elements among those of both. It dominates both antichains, and is dominated
+    /// by any other antichain that dominates both.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::Antichain;
+    /// use timely::order::Product;
+    ///
+    /// let mut frontier1 = Antichain::new();
+    /// frontier1.extend(vec![Product::new(0, 4), Product::new(4, 0)]);
+    /// let mut frontier2 = Antichain::new();
+    /// frontier2.extend(vec![Product::new(2, 2), Product::new(5, 0)]);
+    ///
+    /// let meet = frontier1.meet(&frontier2);
+    /// assert_eq!(meet.elements(), &[Product::new(0, 4), Product::new(4, 0), Product::new(2, 2)]);
+    ///
+    /// // Absorption: the meet with a join leaves an antichain unchanged.
+    /// let absorbed = frontier1.meet(&frontier1.join(&frontier2));
+    /// assert!(absorbed.dominates(&frontier1) && frontier1.dominates(&absorbed));
+    ///```
+    pub fn meet(&self, other: &Antichain<T, R>) -> Antichain<T, R> where T: Clone {
+        let mut result = Antichain::with_reducer();
+        result.extend(self.elements.iter().cloned());
+        result.extend(other.elements.iter().cloned());
+        result
+    }
+
+    /// The least upper bound of two antichains, viewed as frontiers.
+    ///
+    /// The result is the frontier of the times at or beyond both `self` and `other`, whose elements
+    /// are the minimal elements among the joins of an element of each. It is dominated by both
+    /// antichains, and dominates any other antichain dominated by both.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::Antichain;
+    /// use timely::order::Product;
+    ///
+    /// let mut frontier1 = Antichain::new();
+    /// frontier1.extend(vec![Product::new(0, 4), Product::new(4, 0)]);
+    /// let mut frontier2 = Antichain::new();
+    /// frontier2.extend(vec![Product::new(2, 2), Product::new(5, 0)]);
+    ///
+    /// // The join (5, 4) of (0, 4) and (5, 0) is greater than the join (2, 4), and is not minimal.
+    /// let mut join = frontier1.join(&frontier2);
+    /// join.sort();
+    /// assert_eq!(join.elements(), &[Product::new(2, 4), Product::new(4, 2), Product::new(5, 0)]);
+    ///
+    /// // Absorption: the join with a meet leaves an antichain unchanged.
+    /// let absorbed = frontier1.join(&frontier1.meet(&frontier2));
+    /// assert!(absorbed.dominates(&frontier1) && frontier1.dominates(&absorbed));
+    ///```
+    pub fn join(&self, other: &Antichain<T, R>) -> Antichain<T, R> where T: Lattice {
+        let mut result = Antichain::with_reducer();
+        for element1 in self.elements.iter() {
+            for element2 in other.elements.iter() {
+                result.insert(element1.join(element2));
+            }
+        }
+        result
     }
 
     /// Returns true if every element of `other` is greater or equal to some element of `self`.
+    ///
+    /// Viewing antichains as frontiers, this tests whether `other` is at or beyond `self`. An
+    /// empty antichain dominates no non-empty antichain, and every antichain dominates the
+    /// empty antichain.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::Antichain;
+    ///
+    /// let frontier1 = Antichain::from_elem(2);
+    /// let frontier2 = Antichain::from_elem(3);
+    ///
+    /// assert!(frontier1.dominates(&frontier2));
+    /// assert!(!frontier2.dominates(&frontier1));
+    ///
+    /// let empty = Antichain::new();
+    /// assert!(frontier1.dominates(&empty));
+    /// assert!(!empty.dominates(&frontier1));
+    ///```
     #[inline]
-    pub fn dominates(&self, other: &Antichain<T>) -> bool {
-        other.elements().iter().all(|t2| self.elements().iter().any(|t1| t1.less_equal(t2)))
+    pub fn dominates(&self, other: &Antichain<T, R>) -> bool {
+        other.elements().iter().all(|t2| self.elements().iter().any(|t1| R::dominates(t1, t2)))
     }
 
     /// Reveals the elements in the antichain.
@@ -178,6 +488,7 @@ impl<T: PartialOrder> Antichain<T> {
 #[derive(Clone, Debug)]
 pub struct MutableAntichain<T: PartialOrder+Ord> {
     dirty: usize,
+    clean: usize,
     updates: Vec<(T, i64)>,
     frontier: Vec<T>,
     changes: ChangeBatch<T>,
@@ -198,6 +509,7 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
     pub fn new() -> MutableAntichain<T> {
         MutableAntichain {
             dirty: 0,
+            clean: 0,
             updates: Vec::new(),
             frontier:  Vec::new(),
             changes: ChangeBatch::new(),
@@ -218,6 +530,7 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
     #[inline]
     pub fn clear(&mut self) {
         self.dirty = 0;
+        self.clean = 0;
         self.updates.clear();
         self.frontier.clear();
         self.changes.clear();
@@ -245,6 +558,35 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
         AntichainRef::new(&self.frontier)
     }
 
+    /// Iterates over the minimal elements with positive count.
+    ///
+    /// An owned copy of the frontier is available as `frontier().to_vec()`.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::MutableAntichain;
+    /// use timely::order::Product;
+    ///
+    /// let mut frontier = MutableAntichain::new();
+    /// frontier.update_iter(vec![
+    ///     (Product::new(1, 1), 2),
+    ///     (Product::new(0, 3), 1),
+    ///     (Product::new(3, 0), 1),
+    ///     (Product::new(1, 1), -2),
+    ///     (Product::new(2, 2), 1),
+    /// ]);
+    /// let mut elements = frontier.frontier_iter().cloned().collect::<Vec<_>>();
+    /// elements.sort();
+    /// assert_eq!(elements, vec![Product::new(0, 3), Product::new(2, 2), Product::new(3, 0)]);
+    /// assert_eq!(frontier.frontier().len(), 3);
+    ///```
+    #[inline]
+    pub fn frontier_iter(&self) -> ::std::slice::Iter<'_, T> {
+        debug_assert_eq!(self.dirty, 0);
+        self.frontier.iter()
+    }
+
     /// Creates a new singleton `MutableAntichain`.
     ///
     /// # Examples
@@ -259,6 +601,7 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
     pub fn new_bottom(bottom: T) -> MutableAntichain<T> {
         MutableAntichain {
             dirty: 0,
+            clean: 1,
             updates: vec![(bottom.clone(), 1)],
             frontier: vec![bottom],
             changes: ChangeBatch::new(),
@@ -376,6 +719,9 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
         if rebuild_required {
             self.rebuild()
         }
+        else {
+            self.maintain_bounds()
+        }
         self.changes.drain()
     }
 
@@ -410,6 +756,35 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
             .for_each(|(time, diff)| action(&time, diff));
     }
 
+    /// Applies updates to the antichain and returns true if the frontier changed.
+    ///
+    /// The changes to the frontier are discarded, and changes that cancel, as when an element
+    /// leaves and returns within the same updates, do not count as changing the frontier. This
+    /// allows callers that only need to know whether to act on a frontier change to avoid
+    /// collecting the changes.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::MutableAntichain;
+    ///
+    /// let mut frontier = MutableAntichain::new_bottom(1u64);
+    ///
+    /// // Counts change, and cancel, without changing the frontier.
+    /// assert!(!frontier.update_iter_changed(vec![(1, 2), (3, 1), (1, -2), (3, -1)]));
+    /// assert!(!frontier.update_iter_changed(vec![(1, -1), (1, 1)]));
+    ///
+    /// assert!(frontier.update_iter_changed(vec![(1, -1), (2, 1)]));
+    /// assert_eq!(frontier.frontier().to_vec(), vec![2]);
+    ///```
+    #[inline]
+    pub fn update_iter_changed<I>(&mut self, updates: I) -> bool
+    where
+        I: IntoIterator<Item = (T, i64)>,
+    {
+        self.update_iter(updates).next().is_some()
+    }
+
     /// Sorts and consolidates `self.updates` and applies `action` to any frontier changes.
     ///
     /// This method is meant to be used for bulk updates to the frontier, and does more work than one might do
@@ -417,17 +792,7 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
     /// especially true when we want to apply very large numbers of updates.
     fn rebuild(&mut self) {
 
-        // sort and consolidate updates; retain non-zero accumulations.
-        if !self.updates.is_empty() {
-            self.updates.sort_by(|x,y| x.0.cmp(&y.0));
-            for i in 0 .. self.updates.len() - 1 {
-                if self.updates[i].0 == self.updates[i+1].0 {
-                    self.updates[i+1].1 += self.updates[i].1;
-                    self.updates[i].1 = 0;
-                }
-            }
-            self.updates.retain(|x| x.1 != 0);
-        }
+        self.consolidate();
 
         for time in self.frontier.drain(..) {
             self.changes.update(time, -1);
@@ -446,6 +811,37 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
         }
     }
 
+    /// Sorts and consolidates `self.updates`, retaining non-zero accumulations.
+    fn consolidate(&mut self) {
+        if !self.updates.is_empty() {
+            self.updates.sort_by(|x,y| x.0.cmp(&y.0));
+            for i in 0 .. self.updates.len() - 1 {
+                if self.updates[i].0 == self.updates[i+1].0 {
+                    self.updates[i+1].1 += self.updates[i].1;
+                    self.updates[i].1 = 0;
+                }
+            }
+            self.updates.retain(|x| x.1 != 0);
+        }
+        self.clean = self.updates.len();
+    }
+
+    /// Consolidates `self.updates` if enough updates have accumulated since the last consolidation.
+    ///
+    /// Updates that do not affect the frontier do not cause a rebuild, and without consolidation
+    /// would accumulate without bound, for example as messages at times beyond the frontier come
+    /// and go. The number of updates is kept to at most twice the number of distinct times.
+    fn maintain_bounds(&mut self) {
+        // if we have more than 32 elements and at least half of them are not clean, consolidate.
+        if self.updates.len() > 32 && self.updates.len() >> 1 >= self.clean {
+            self.consolidate();
+            // release memory retained from a large burst of updates.
+            if self.updates.capacity() > 4 * self.updates.len() + 32 {
+                self.updates.shrink_to_fit();
+            }
+        }
+    }
+
     /// Reports the count for a queried time.
     pub fn count_for(&self, query_time: &T) -> i64 {
         self.updates
@@ -454,6 +850,48 @@ impl<T: PartialOrder+Ord+Clone> MutableAntichain<T> {
             .map(|td| td.1)
             .sum()
     }
+
+    /// Reports the updates that make up the accumulated counts.
+    ///
+    /// The updates are not necessarily consolidated: a time may appear multiple times, and
+    /// with a net count of zero.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::MutableAntichain;
+    ///
+    /// let mut frontier = MutableAntichain::new_bottom(1u64);
+    /// frontier.update_iter(vec![(2, 3), (1, -1)]);
+    /// let count = frontier.updates().filter(|x| x.0 == 2).map(|x| x.1).sum::<i64>();
+    /// assert_eq!(count, 3);
+    ///
+    /// // Updates are consolidated as they accumulate.
+    /// for _ in 0 .. 1000 {
+    ///     frontier.update_iter(vec![(5, 1), (5, -1)]);
+    /// }
+    /// assert!(frontier.updates().count() <= 64);
+    ///```
+    pub fn updates(&self) -> impl Iterator<Item=&(T, i64)> {
+        self.updates.iter()
+    }
+
+    /// Reports the sum of the absolute values of the accumulated counts of all times.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::MutableAntichain;
+    ///
+    /// let mut frontier = MutableAntichain::new_bottom(1u64);
+    /// frontier.update_iter(vec![(2, 3), (3, -1), (2, -1)]);
+    /// assert_eq!(frontier.absolute_count(), 4);
+    ///```
+    pub fn absolute_count(&self) -> i64 {
+        let mut counts = ChangeBatch::new();
+        counts.extend(self.updates.iter().cloned());
+        counts.into_inner().into_iter().map(|(_, diff)| diff.abs()).sum()
+    }
 }
 
 /// Extension trait for filtering time changes through antichains.
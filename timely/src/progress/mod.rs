@@ -11,8 +11,11 @@ pub mod frontier;
 pub mod timestamp;
 pub mod operate;
 pub mod broadcast;
+pub mod delayed;
 pub mod reachability;
 pub mod subgraph;
+pub mod testing;
+pub mod wire;
 
 /// A timely dataflow location.
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Abomonation, Serialize, Deserialize)]
@@ -4,7 +4,7 @@ use std::rc::Rc;
 use std::cell::RefCell;
 
 use crate::scheduling::Schedule;
-use crate::progress::{Timestamp, ChangeBatch, Antichain};
+use crate::progress::{Timestamp, ChangeBatch, Antichain, PathSummary};
 
 /// Methods for describing an operators topology, and the progress it makes.
 pub trait Operate<T: Timestamp> : Schedule {
@@ -58,10 +58,34 @@ pub trait Operate<T: Timestamp> : Schedule {
     fn notify_me(&self) -> bool { true }
 }
 
+/// Indicates whether `summary` advances a time at some input to at most `time` on `output`.
+///
+/// The candidate times are listed with their inputs, and are typically the input frontier of an
+/// operator and the times of messages it has consumed. Apart from a capability it already holds
+/// for `output` at a time less or equal to `time`, these are what may justify an operator acquiring
+/// a capability or producing a message at `time`.
+pub(crate) fn summary_justifies<'a, T, I>(summary: &[Vec<Antichain<T::Summary>>], output: usize, time: &T, candidates: I) -> bool
+where
+    T: Timestamp+'a,
+    I: IntoIterator<Item=(usize, &'a T)>,
+{
+    candidates.into_iter().any(|(input, from)| {
+        summary[input][output]
+            .elements()
+            .iter()
+            .any(|s| s.results_in(from).map(|t| t.less_equal(time)).unwrap_or(false))
+    })
+}
+
 /// Progress information shared between parent and child.
 #[derive(Debug)]
 pub struct SharedProgress<T: Timestamp> {
     /// Frontier capability changes reported by the parent scope.
+    ///
+    /// Only changes to the frontiers are reported, rather than the frontiers themselves, so that the
+    /// cost of communicating a frontier is proportional to how much of it changes rather than to its
+    /// size. An operator that needs its input frontiers maintains them in place, typically as a
+    /// `MutableAntichain` to which it applies these changes, and reads them from there.
     pub frontiers: Vec<ChangeBatch<T>>,
     /// Consumed message changes reported by the child operator.
     pub consumeds: Vec<ChangeBatch<T>>,
@@ -81,4 +105,151 @@ impl<T: Timestamp> SharedProgress<T> {
             produceds: vec![ChangeBatch::new(); outputs],
         }
     }
+
+    /// Drains the progress statements reported by the child operator into `sink`.
+    ///
+    /// Statements are consolidated, and are presented port by port: first consumed messages
+    /// for each input, then internal capability changes and produced messages for each output.
+    /// Frontier changes reported by the parent scope are left in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::progress::operate::{SharedProgress, ProgressSink};
+    ///
+    /// #[derive(Default)]
+    /// struct Recorder {
+    ///     consumeds: Vec<(usize, u64, i64)>,
+    ///     internals: Vec<(usize, u64, i64)>,
+    ///     produceds: Vec<(usize, u64, i64)>,
+    /// }
+    ///
+    /// impl ProgressSink<u64> for Recorder {
+    ///     fn on_consumed(&mut self, input: usize, time: u64, count: i64) { self.consumeds.push((input, time, count)); }
+    ///     fn on_internal(&mut self, output: usize, time: u64, count: i64) { self.internals.push((output, time, count)); }
+    ///     fn on_produced(&mut self, output: usize, time: u64, count: i64) { self.produceds.push((output, time, count)); }
+    /// }
+    ///
+    /// // Record the same statements twice.
+    /// let mut progress = vec![SharedProgress::<u64>::new(2, 2), SharedProgress::<u64>::new(2, 2)];
+    /// for shared in progress.iter_mut() {
+    ///     shared.consumeds[1].update(3, 2);
+    ///     shared.internals[0].update(0, -1);
+    ///     shared.internals[0].update(4, 1);
+    ///     shared.produceds[0].update(3, 1);
+    ///     shared.produceds[1].update(5, 1);
+    ///     shared.produceds[1].update(5, 1);
+    /// }
+    ///
+    /// let mut recorder = Recorder::default();
+    /// progress[0].drain_into(&mut recorder);
+    ///
+    /// // The sink observes exactly what draining the batches would produce.
+    /// let drain = |batches: &mut Vec<timely::progress::ChangeBatch<u64>>| {
+    ///     batches.iter_mut()
+    ///            .enumerate()
+    ///            .flat_map(|(port, batch)| batch.drain().map(move |(t, d)| (port, t, d)))
+    ///            .collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(recorder.consumeds, drain(&mut progress[1].consumeds));
+    /// assert_eq!(recorder.internals, drain(&mut progress[1].internals));
+    /// assert_eq!(recorder.produceds, drain(&mut progress[1].produceds));
+    /// assert_eq!(recorder.produceds, vec![(0, 3, 1), (1, 5, 2)]);
+    /// ```
+    pub fn drain_into(&mut self, sink: &mut dyn ProgressSink<T>) {
+        self.drain_into_sink(sink);
+    }
+
+    /// Drains the progress statements reported by the child operator into an owned report.
+    ///
+    /// The report contains exactly the statements `drain_into` would present to a sink.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::progress::operate::SharedProgress;
+    ///
+    /// let mut shared = SharedProgress::<u64>::new(1, 1);
+    /// shared.consumeds[0].update(2, 1);
+    /// shared.internals[0].update(0, -1);
+    /// shared.internals[0].update(3, 1);
+    /// shared.produceds[0].update(2, 1);
+    /// shared.produceds[0].update(2, 1);
+    ///
+    /// let report = shared.report();
+    /// assert_eq!(report.consumeds, vec![vec![(2, 1)]]);
+    /// assert_eq!(report.internals, vec![vec![(0, -1), (3, 1)]]);
+    /// assert_eq!(report.produceds, vec![vec![(2, 2)]]);
+    ///
+    /// // Statements are drained, and reported only once.
+    /// assert_eq!(shared.report(), timely::progress::operate::ProgressReport::new(1, 1));
+    /// ```
+    pub fn report(&mut self) -> ProgressReport<T> {
+        let mut report = ProgressReport::new(self.consumeds.len(), self.internals.len());
+        self.drain_into_sink(&mut report);
+        report
+    }
+
+    fn drain_into_sink<S: ProgressSink<T>+?Sized>(&mut self, sink: &mut S) {
+        for (input, consumed) in self.consumeds.iter_mut().enumerate() {
+            for (time, count) in consumed.drain() {
+                sink.on_consumed(input, time, count);
+            }
+        }
+        for (output, internal) in self.internals.iter_mut().enumerate() {
+            for (time, count) in internal.drain() {
+                sink.on_internal(output, time, count);
+            }
+        }
+        for (output, produced) in self.produceds.iter_mut().enumerate() {
+            for (time, count) in produced.drain() {
+                sink.on_produced(output, time, count);
+            }
+        }
+    }
+}
+
+/// An owned collection of the progress statements reported by an operator.
+///
+/// Statements are consolidated and listed port by port, with the same meaning as the corresponding
+/// fields of `SharedProgress`; `internals` describes changes to the capabilities the operator holds,
+/// and so to the frontier of its outputs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgressReport<T> {
+    /// Consumed message changes, for each input.
+    pub consumeds: Vec<Vec<(T, i64)>>,
+    /// Internal capability changes, for each output.
+    pub internals: Vec<Vec<(T, i64)>>,
+    /// Produced message changes, for each output.
+    pub produceds: Vec<Vec<(T, i64)>>,
+}
+
+impl<T> ProgressReport<T> {
+    /// Allocates an empty report for the given numbers of inputs and outputs.
+    pub fn new(inputs: usize, outputs: usize) -> Self {
+        ProgressReport {
+            consumeds: (0 .. inputs).map(|_| Vec::new()).collect(),
+            internals: (0 .. outputs).map(|_| Vec::new()).collect(),
+            produceds: (0 .. outputs).map(|_| Vec::new()).collect(),
+        }
+    }
+}
+
+impl<T> ProgressSink<T> for ProgressReport<T> {
+    fn on_consumed(&mut self, input: usize, time: T, count: i64) { self.consumeds[input].push((time, count)); }
+    fn on_internal(&mut self, output: usize, time: T, count: i64) { self.internals[output].push((time, count)); }
+    fn on_produced(&mut self, output: usize, time: T, count: i64) { self.produceds[output].push((time, count)); }
+}
+
+/// A recipient of the progress statements reported by an operator.
+///
+/// A sink allows a consumer of progress statements to observe them as they are drained,
+/// rather than interpreting the layout of `SharedProgress`.
+pub trait ProgressSink<T> {
+    /// Observes a change in the number of messages consumed at `time` on `input`.
+    fn on_consumed(&mut self, input: usize, time: T, count: i64);
+    /// Observes a change in the number of capabilities held at `time` for `output`.
+    fn on_internal(&mut self, output: usize, time: T, count: i64);
+    /// Observes a change in the number of messages produced at `time` on `output`.
+    fn on_produced(&mut self, output: usize, time: T, count: i64);
 }
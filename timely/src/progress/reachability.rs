@@ -72,9 +72,11 @@
 //! assert_eq!(results[2], ((Location::new_target(2, 0), 17), -1));
 //! ```
 
-use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::cmp::Reverse;
 
+use abomonation::Abomonation;
+
 use crate::progress::Timestamp;
 use crate::progress::{Source, Target};
 use crate::progress::ChangeBatch;
@@ -83,6 +85,19 @@ use crate::progress::{Location, Port};
 use crate::progress::frontier::{Antichain, MutableAntichain};
 use crate::progress::timestamp::PathSummary;
 
+/// Path summaries from graph locations to each scope output, indexed by output.
+pub type OutputSummaries<T> = HashMap<Location, Vec<Antichain<<T as Timestamp>::Summary>>>;
+
+/// Path summaries from each scope input to each scope output, indexed by input and then output.
+pub type ScopeSummary<T> = Vec<Vec<Antichain<<T as Timestamp>::Summary>>>;
+
+/// The serialized form of a builder: node shapes, node summaries, edges, and output summaries.
+type Definition<S> = (
+    Vec<(usize, usize)>,
+    Vec<Vec<Vec<Vec<S>>>>,
+    Vec<Vec<Vec<(usize, usize)>>>,
+    Vec<(Location, Vec<Vec<S>>)>,
+);
 
 /// A topology builder, which can summarize reachability along paths.
 ///
@@ -102,6 +117,12 @@ use crate::progress::timestamp::PathSummary;
 /// not be totally ordered (e.g., "increment the timestamp" and "take the maximum of
 /// the timestamp and seven").
 ///
+/// Paths around loops are summarized by their least summaries only. A path that may go around
+/// a loop arbitrarily many times is represented by its summary with the fewest iterations, as
+/// the summaries of longer paths are greater and are not retained. Such a summary indicates
+/// that the destination may be reached at any time greater or equal to its result, which is
+/// also why the compilation of summaries terminates for graphs with loops.
+///
 /// # Examples
 ///
 /// ```rust
@@ -156,6 +177,11 @@ impl<T: Timestamp> Builder<T> {
     /// Add links internal to operators.
     ///
     /// This method overwrites any existing summary, instead of anything more sophisticated.
+    ///
+    /// Each summary from an input to an output is an antichain, and may contain several
+    /// incomparable summaries when a timestamp may be advanced along the connection in more
+    /// than one way. Edges do not advance timestamps, so a connection that may advance them in
+    /// several ways is described by an operator with such a summary.
     pub fn add_node(&mut self, index: usize, inputs: usize, outputs: usize, summary: Vec<Vec<Antichain<T::Summary>>>) {
 
         // Assert that all summaries exist.
@@ -203,6 +229,317 @@ impl<T: Timestamp> Builder<T> {
         Tracker::allocate_from(self)
     }
 
+    /// Computes path summaries from each location to each scope output.
+    ///
+    /// The summaries depend only on the topology, and can be supplied to `build_from_summaries`
+    /// for builders with the same nodes and edges, avoiding their recomputation.
+    pub fn summarize(&self) -> OutputSummaries<T> {
+        summarize_outputs::<T>(&self.nodes, &self.edges)
+    }
+
+    /// Computes path summaries as `summarize` does, recording how they are derived.
+    ///
+    /// Each summary added during the computation is appended to `trace` as the location, the scope
+    /// output, and the summary from the location to the output, in the order they are added. Each
+    /// extends a previously added summary, either back along an edge or back through an operator.
+    /// Summaries added may later be found to be dominated, and be absent from the result.
+    pub fn summarize_traced(&self, trace: &mut Vec<(Location, usize, T::Summary)>) -> OutputSummaries<T> {
+        summarize_outputs_counted::<T>(&self.nodes, &self.edges, Some(trace)).0
+    }
+
+    /// Computes path summaries from each location to each scope output, along paths through
+    /// nodes that satisfy `predicate`.
+    ///
+    /// Paths may only pass through an operator whose index satisfies `predicate`; the scope
+    /// itself, node zero, is always considered. Locations of other operators are absent from
+    /// the result, and the builder itself is not modified.
+    pub fn summarize_filtered<P: Fn(usize) -> bool>(&self, predicate: P) -> OutputSummaries<T> {
+        let nodes =
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, summary)| {
+                if index == 0 || predicate(index) { summary.clone() }
+                else { summary.iter().map(|outputs| vec![Antichain::new(); outputs.len()]).collect() }
+            })
+            .collect::<Vec<_>>();
+
+        let mut summaries = summarize_outputs::<T>(&nodes, &self.edges);
+        summaries.retain(|location, _| location.node == 0 || predicate(location.node));
+        summaries
+    }
+
+    /// Updates path summaries computed by `summarize` after changes at `node`.
+    ///
+    /// The changes may be to the internal summary of `node`, as when the edges within a nested
+    /// subgraph change, or to the edges from its outputs. Only the summaries of locations from
+    /// which `node` is reachable may change, and only those are recomputed, starting from the
+    /// unchanged summaries of the locations they lead to. The result is equivalent to that of
+    /// `summarize`, which should be used instead when changes are made at several nodes.
+    pub fn update_summaries(&self, summaries: &mut OutputSummaries<T>, node: usize) {
+
+        // Locations from which `node` is reachable, found by walking back up the graph.
+        let reverse = reverse_edges(&self.edges);
+        let mut stale = HashSet::new();
+        let mut todo = Vec::new();
+        for port in 0 .. self.shape[node].0 { todo.push(Location::new_target(node, port)); }
+        for port in 0 .. self.shape[node].1 { todo.push(Location::new_source(node, port)); }
+        while let Some(location) = todo.pop() {
+            if stale.insert(location) {
+                match location.port {
+                    Port::Source(output) => {
+                        for (input, summaries) in self.nodes[location.node].iter().enumerate() {
+                            if !summaries[output].elements().is_empty() {
+                                todo.push(Location::new_target(location.node, input));
+                            }
+                        }
+                    },
+                    Port::Target(_) => {
+                        todo.extend(reverse.get(&location).into_iter().flatten().cloned());
+                    },
+                }
+            }
+        }
+
+        for location in stale.iter() {
+            summaries.remove(location);
+        }
+
+        // Re-derive stale summaries from the locations they lead to that are not stale.
+        let mut worklist = VecDeque::new();
+        let mut insert = |summaries: &mut OutputSummaries<T>, location: Location, output: usize, summary: T::Summary| {
+            let antichains = summaries.entry(location).or_default();
+            while antichains.len() <= output { antichains.push(Antichain::new()); }
+            if antichains[output].insert(summary.clone()) {
+                worklist.push_back((location, output, summary, 0));
+            }
+        };
+        for location in stale.iter() {
+            match location.port {
+                Port::Source(port) => {
+                    for target in self.edges[location.node][port].iter() {
+                        let next = Location::from(*target);
+                        if target.node == 0 {
+                            insert(summaries, *location, target.port, Default::default());
+                        }
+                        else if !stale.contains(&next) {
+                            let known = summaries.get(&next).cloned().unwrap_or_default();
+                            for (output, antichain) in known.into_iter().enumerate() {
+                                for summary in antichain.elements().iter() {
+                                    insert(summaries, *location, output, summary.clone());
+                                }
+                            }
+                        }
+                    }
+                },
+                Port::Target(port) => {
+                    for (output_port, internal) in self.nodes[location.node][port].iter().enumerate() {
+                        let next = Location::new_source(location.node, output_port);
+                        if !stale.contains(&next) {
+                            let known = summaries.get(&next).cloned().unwrap_or_default();
+                            for (output, antichain) in known.into_iter().enumerate() {
+                                for summary in antichain.elements().iter() {
+                                    for operator_summary in internal.elements().iter() {
+                                        if let Some(combined) = operator_summary.followed_by(summary) {
+                                            insert(summaries, *location, output, combined);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+
+        extend_summaries::<T>(&self.nodes, &reverse, summaries, worklist, None);
+    }
+
+    /// Compiles the current nodes and edges using precomputed path summaries.
+    ///
+    /// The summaries must be those `summarize` would produce for this builder, which is
+    /// checked in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use timely::progress::frontier::Antichain;
+    /// use timely::progress::{Source, Target};
+    /// use timely::progress::reachability::Builder;
+    ///
+    /// let mut builder = Builder::<usize>::new();
+    /// builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+    /// builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+    /// builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+    /// builder.add_edge(Source::new(1, 0), Target::new(0, 0));
+    ///
+    /// // Summarize once, and build several trackers from the summaries.
+    /// let summaries = builder.summarize();
+    /// let (mut tracker1, scope1) = builder.build();
+    /// let (mut tracker2, scope2) = builder.build_from_summaries(summaries);
+    /// assert_eq!(scope1, scope2);
+    ///
+    /// tracker1.update_source(Source::new(1, 0), 17, 1);
+    /// tracker2.update_source(Source::new(1, 0), 17, 1);
+    /// tracker1.propagate_all();
+    /// tracker2.propagate_all();
+    ///
+    /// let results1 = tracker1.pushed_output()[0].drain().collect::<Vec<_>>();
+    /// let results2 = tracker2.pushed_output()[0].drain().collect::<Vec<_>>();
+    /// assert_eq!(results1, vec![(17, 1)]);
+    /// assert_eq!(results1, results2);
+    /// ```
+    pub fn build_from_summaries(&self, summaries: OutputSummaries<T>) -> (Tracker<T>, ScopeSummary<T>) {
+        debug_assert!(equivalent_summaries::<T>(&summaries, &self.summarize()), "Installed summaries differ from computed summaries");
+        Tracker::allocate_from_summaries(self, summaries)
+    }
+
+    /// Compiles the nodes and edges, keeping a single path summary from each location to each output.
+    ///
+    /// Where `build` retains the antichain of all incomparable summaries from a location to a scope
+    /// output, this method folds each antichain into one summary using `meet`, which should return
+    /// a summary less or equal to both of its arguments. The single summary is then no greater than
+    /// any it replaces, so the output capabilities the tracker projects are no later than those of
+    /// `build`, though they may be earlier; they are the same when each antichain has one element.
+    /// This bounds the summaries kept for each location, at the cost of precision. Input frontiers
+    /// of operators are propagated along the internal summaries of the nodes, and are unaffected.
+    pub fn build_with_meet<M>(&self, meet: M) -> (Tracker<T>, ScopeSummary<T>)
+    where
+        M: Fn(&T::Summary, &T::Summary) -> T::Summary,
+    {
+        let mut summaries = self.summarize();
+        for antichains in summaries.values_mut() {
+            for antichain in antichains.iter_mut() {
+                let mut elements = antichain.elements().iter();
+                if let Some(first) = elements.next() {
+                    let folded = elements.fold(first.clone(), |folded, summary| meet(&folded, summary));
+                    *antichain = Antichain::from_elem(folded);
+                }
+            }
+        }
+        Tracker::allocate_from_summaries(self, summaries)
+    }
+
+    /// Serializes the topology and the path summaries it implies.
+    ///
+    /// The result records the shape and internal summaries of each node, the edges, and the
+    /// summaries `summarize` produces, and can be reloaded with `from_definition`. No progress
+    /// tracking state is recorded, as builders have none.
+    ///
+    /// The result is in the in-memory layout of the definition, as produced by `abomonation`, and
+    /// should only be reloaded by the same program for the same timestamp type. It is not suitable
+    /// for storage or exchange with untrusted parties; see the safety notes of `from_definition`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use timely::progress::frontier::Antichain;
+    /// use timely::progress::{Source, Target};
+    /// use timely::progress::reachability::Builder;
+    ///
+    /// let mut builder = Builder::<usize>::new();
+    /// builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+    /// builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(0)]]);
+    /// builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+    /// builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+    /// builder.add_edge(Source::new(1, 0), Target::new(2, 0));
+    /// builder.add_edge(Source::new(2, 0), Target::new(0, 0));
+    ///
+    /// // Reload the definition, and build a tracker without recomputing summaries.
+    /// let bytes = builder.serialize_definition();
+    /// let (reloaded, summaries) = unsafe { Builder::<usize>::from_definition(&bytes) }.unwrap();
+    ///
+    /// let (mut tracker1, scope1) = builder.build();
+    /// let (mut tracker2, scope2) = reloaded.build_from_summaries(summaries);
+    /// assert_eq!(scope1, scope2);
+    ///
+    /// tracker1.update_source(Source::new(1, 0), 3, 1);
+    /// tracker2.update_source(Source::new(1, 0), 3, 1);
+    /// tracker1.propagate_all();
+    /// tracker2.propagate_all();
+    ///
+    /// let mut results1 = tracker1.pushed().drain().collect::<Vec<_>>();
+    /// let mut results2 = tracker2.pushed().drain().collect::<Vec<_>>();
+    /// results1.sort();
+    /// results2.sort();
+    /// assert_eq!(results1, results2);
+    /// assert_eq!(tracker1.pushed_output()[0].drain().collect::<Vec<_>>(), vec![(4, 1)]);
+    /// assert_eq!(tracker2.pushed_output()[0].drain().collect::<Vec<_>>(), vec![(4, 1)]);
+    /// ```
+    pub fn serialize_definition(&self) -> Vec<u8> where T::Summary: Abomonation {
+
+        let antichains = |antichains: &Vec<Antichain<T::Summary>>| {
+            antichains.iter().map(|antichain| antichain.elements().to_vec()).collect::<Vec<_>>()
+        };
+
+        let definition: Definition<T::Summary> = (
+            self.shape.clone(),
+            self.nodes.iter().map(|inputs| inputs.iter().map(antichains).collect()).collect(),
+            self.edges.iter().map(|outputs| outputs.iter().map(|targets| targets.iter().map(|t| (t.node, t.port)).collect()).collect()).collect(),
+            self.summarize().iter().map(|(location, summaries)| (*location, antichains(summaries))).collect(),
+        );
+
+        let mut bytes = Vec::new();
+        unsafe { ::abomonation::encode(&definition, &mut bytes).expect("Definition abomonation failed"); }
+        bytes
+    }
+
+    /// Reconstructs a builder and its path summaries from the output of `serialize_definition`.
+    ///
+    /// The summaries may be supplied to `build_from_summaries` on the reconstructed builder. The
+    /// result is `None` if `bytes` are too short to hold a definition, or if the definition they
+    /// hold does not describe a well-formed topology.
+    ///
+    /// # Safety
+    ///
+    /// The bytes are decoded with `abomonation`, which trusts their contents. They must be exactly
+    /// the output of `serialize_definition` for a builder with the same timestamp type, produced
+    /// by the same program; other bytes may result in undefined behavior rather than `None`. The
+    /// bytes are copied into an aligned buffer, and need not themselves be aligned.
+    pub unsafe fn from_definition(bytes: &[u8]) -> Option<(Self, OutputSummaries<T>)> where T::Summary: Abomonation {
+
+        // Decoding casts the start of the buffer to a definition, so the buffer must be aligned.
+        let mut aligned = vec![0u64; bytes.len().div_ceil(8)];
+        let buffer = ::std::slice::from_raw_parts_mut(aligned.as_mut_ptr() as *mut u8, bytes.len());
+        buffer.copy_from_slice(bytes);
+
+        let (definition, rest) = ::abomonation::decode::<Definition<T::Summary>>(buffer)?;
+        if !rest.is_empty() { return None; }
+        let (shape, nodes, edges, summaries) = definition;
+
+        let antichains = |antichains: &Vec<Vec<T::Summary>>| {
+            antichains
+                .iter()
+                .map(|elements| {
+                    let mut antichain = Antichain::new();
+                    for element in elements.iter() { antichain.insert(element.clone()); }
+                    antichain
+                })
+                .collect::<Vec<_>>()
+        };
+
+        if shape.len() != nodes.len() || shape.len() != edges.len() { return None; }
+
+        let mut builder = Builder::new();
+        for (index, &(inputs, outputs)) in shape.iter().enumerate() {
+            let summary = nodes[index].iter().map(antichains).collect::<Vec<_>>();
+            if summary.len() != inputs || summary.iter().any(|x| x.len() != outputs) { return None; }
+            if edges[index].len() != outputs { return None; }
+            builder.add_node(index, inputs, outputs, summary);
+        }
+        for (index, outputs) in edges.iter().enumerate() {
+            for (output, targets) in outputs.iter().enumerate() {
+                for &(node, port) in targets.iter() {
+                    if node >= shape.len() || port >= shape[node].0 { return None; }
+                    builder.add_edge(Source::new(index, output), Target::new(node, port));
+                }
+            }
+        }
+
+        let summaries = summaries.iter().map(|(location, summaries)| (*location, antichains(summaries))).collect();
+        Some((builder, summaries))
+    }
+
     /// Tests whether the graph a cycle of default path summaries.
     ///
     /// Graphs containing cycles of default path summaries will most likely
@@ -327,6 +664,7 @@ impl<T: Timestamp> Builder<T> {
 /// A `Tracker` tracks, for a fixed graph topology, the implications of
 /// pointstamp changes at various node input and output ports. These changes may
 /// alter the potential pointstamps that could arrive at downstream input ports.
+#[derive(Clone)]
 pub struct Tracker<T:Timestamp> {
 
     /// Internal connections within hosted operators.
@@ -375,9 +713,13 @@ pub struct Tracker<T:Timestamp> {
     /// always be exactly equal to the sum across all operators of the frontier sizes
     /// of the target and source `pointstamps` member.
     total_counts: i64,
+
+    /// The rounds and changes taken to compile the summaries, if the tracker compiled them.
+    summary_stats: (usize, usize),
 }
 
 /// Target and source information for each operator.
+#[derive(Clone)]
 pub struct PerOperator<T: Timestamp> {
     /// Port information for each target.
     pub targets: Vec<PortInformation<T>>,
@@ -465,6 +807,18 @@ impl<T:Timestamp> Tracker<T> {
     /// The result is a pair of tracker, and the summaries from each input port to each
     /// output port.
     pub fn allocate_from(builder: &Builder<T>) -> (Self, Vec<Vec<Antichain<T::Summary>>>) {
+        let (output_summaries, summary_stats) = summarize_outputs_counted::<T>(&builder.nodes, &builder.edges, None);
+        let (mut tracker, builder_summary) = Tracker::allocate_from_summaries(builder, output_summaries);
+        tracker.summary_stats = summary_stats;
+        (tracker, builder_summary)
+    }
+
+    /// Allocate a new `Tracker` using the shape from `summaries` and precomputed output summaries.
+    fn allocate_from_summaries(
+        builder: &Builder<T>,
+        output_summaries: OutputSummaries<T>,
+    ) -> (Self, Vec<Vec<Antichain<T::Summary>>>)
+    {
 
         // Allocate buffer space for each input and input port.
         let mut per_operator =
@@ -477,8 +831,7 @@ impl<T:Timestamp> Tracker<T> {
         // Summary of scope inputs to scope outputs.
         let mut builder_summary = vec![vec![]; builder.shape[0].1];
 
-        // Compile summaries from each location to each scope output.
-        let output_summaries = summarize_outputs::<T>(&builder.nodes, &builder.edges);
+        // Install summaries from each location to each scope output.
         for (location, summaries) in output_summaries.into_iter() {
             // Summaries from scope inputs are useful in summarizing the scope.
             if location.node == 0 {
@@ -516,6 +869,7 @@ impl<T:Timestamp> Tracker<T> {
             pushed_changes: ChangeBatch::new(),
             output_changes,
             total_counts: 0,
+            summary_stats: (0, 0),
         };
 
         (tracker, builder_summary)
@@ -526,6 +880,51 @@ impl<T:Timestamp> Tracker<T> {
     /// The method drains `self.input_changes` and circulates their implications
     /// until we cease deriving new implications.
     pub fn propagate_all(&mut self) {
+        self.propagate_all_with(|_from, _to, _time, _diff| { });
+    }
+
+    /// Propagates all pending updates, reporting each step of the propagation.
+    ///
+    /// Before a change in the frontier at one location is propagated to another, along an edge
+    /// or the internal summary of an operator, `trace` is invoked with the two locations, the
+    /// time resulting at the second location, and the change. The results are otherwise the
+    /// same as those of `propagate_all`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use timely::progress::frontier::Antichain;
+    /// use timely::progress::{Location, Source, Target};
+    /// use timely::progress::reachability::Builder;
+    ///
+    /// let mut builder = Builder::<usize>::new();
+    /// builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+    /// builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+    /// builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+    /// builder.add_edge(Source::new(1, 0), Target::new(0, 0));
+    ///
+    /// let (mut tracker, _) = builder.build();
+    /// tracker.update_source(Source::new(0, 0), 5, 1);
+    ///
+    /// let mut trace = Vec::new();
+    /// tracker.propagate_all_traced(&mut |from, to, time, diff| trace.push((from, to, time, diff)));
+    ///
+    /// // The change crosses an edge, then the operator, then another edge.
+    /// assert_eq!(trace, vec![
+    ///     (Location::new_source(0, 0), Location::new_target(1, 0), 5, 1),
+    ///     (Location::new_target(1, 0), Location::new_source(1, 0), 6, 1),
+    ///     (Location::new_source(1, 0), Location::new_target(0, 0), 6, 1),
+    /// ]);
+    /// ```
+    pub fn propagate_all_traced(&mut self, trace: &mut dyn FnMut(Location, Location, T, i64)) {
+        self.propagate_all_with(|from, to, time, diff| trace(from, to, time.clone(), diff));
+    }
+
+    /// Propagates all pending updates, invoking `trace` before each propagation step.
+    ///
+    /// The time is presented by reference, so that untraced propagation need not copy it.
+    #[inline]
+    fn propagate_all_with<F: FnMut(Location, Location, &T, i64)>(&mut self, mut trace: F) {
 
         // Step 1: Drain `self.input_changes` and determine actual frontier changes.
         //
@@ -604,6 +1003,7 @@ impl<T:Timestamp> Tracker<T> {
                                 let source = Location { node: location.node, port: Port::Source(output_port) };
                                 for summary in summaries.elements().iter() {
                                     if let Some(new_time) = summary.results_in(&time) {
+                                        trace(location, source, &new_time, diff);
                                         self.worklist.push(Reverse((new_time, source, diff)));
                                     }
                                 }
@@ -623,6 +1023,7 @@ impl<T:Timestamp> Tracker<T> {
 
                         for (time, diff) in changes {
                             for new_target in self.edges[location.node][port_index].iter() {
+                                trace(location, Location::from(*new_target), &time, diff);
                                 self.worklist.push(Reverse((
                                     time.clone(),
                                     Location::from(*new_target),
@@ -637,6 +1038,62 @@ impl<T:Timestamp> Tracker<T> {
         }
     }
 
+    /// Releases excess storage held by the compiled path summaries.
+    ///
+    /// Summaries are accumulated port by port while they are compiled, and may retain spare
+    /// capacity as well as trailing empty antichains for scope outputs a port cannot reach.
+    /// This method removes those antichains and shrinks the storage to fit, which does not
+    /// change the reachability the tracker reports.
+    pub fn compact_summaries(&mut self) {
+        for operator in self.per_operator.iter_mut() {
+            for port in operator.targets.iter_mut().chain(operator.sources.iter_mut()) {
+                while port.output_summaries.last().map(|x| x.elements().is_empty()).unwrap_or(false) {
+                    port.output_summaries.pop();
+                }
+                port.output_summaries.shrink_to_fit();
+            }
+        }
+    }
+
+    /// Replaces the path summaries from each target to each scope output with `summaries`.
+    ///
+    /// The summaries are indexed by node, then input port, then scope output, and replace those
+    /// derived from the graph by `Builder::build`. This supports models in which the latencies
+    /// between ports are known a priori, rather than derived from the operators' summaries. The
+    /// summaries only affect the projection of target pointstamps to the scope outputs, and not
+    /// the frontiers propagated between operators. The entries for node zero, whose targets are
+    /// the scope outputs, must have the correct shape but are otherwise ignored, as by `build`.
+    ///
+    /// This method panics if `summaries` does not match the shape of the graph, or if the tracker
+    /// is tracking any pointstamps, whose projections would otherwise be inconsistent.
+    pub fn install_target_summaries(&mut self, summaries: Vec<Vec<Vec<Antichain<T::Summary>>>>) {
+        assert!(!self.tracking_anything(), "Target summaries installed while tracking pointstamps");
+        assert_eq!(summaries.len(), self.per_operator.len(), "Summaries for {} nodes, expected {}", summaries.len(), self.per_operator.len());
+        let outputs = self.output_changes.len();
+        for (node, (operator, summaries)) in self.per_operator.iter().zip(summaries.iter()).enumerate() {
+            assert_eq!(summaries.len(), operator.targets.len(), "Summaries for {} inputs of node {}, expected {}", summaries.len(), node, operator.targets.len());
+            for (port, summaries) in summaries.iter().enumerate() {
+                assert_eq!(summaries.len(), outputs, "Summaries for {} outputs from input {} of node {}, expected {}", summaries.len(), port, node, outputs);
+            }
+        }
+        for (operator, summaries) in self.per_operator.iter_mut().zip(summaries).skip(1) {
+            for (port, summaries) in operator.targets.iter_mut().zip(summaries) {
+                port.output_summaries = summaries;
+            }
+        }
+    }
+
+    /// The number of rounds and of changes taken to compile the tracker's path summaries.
+    ///
+    /// A round extends the summaries through one more operator, so the number of rounds is the
+    /// greatest number of operators along a path whose least summary was compiled, and a deep
+    /// topology takes many rounds. A change adds a summary to the antichain at some location, and
+    /// a dense topology makes many changes in few rounds. Trackers built from precomputed
+    /// summaries report zero rounds and changes.
+    pub fn summary_stats(&self) -> (usize, usize) {
+        self.summary_stats
+    }
+
     /// Implications of maintained capabilities projected to each output.
     pub fn pushed_output(&mut self) -> &mut [ChangeBatch<T>] {
         &mut self.output_changes[..]
@@ -652,6 +1109,43 @@ impl<T:Timestamp> Tracker<T> {
         &self.per_operator[index]
     }
 
+    /// Copies the progress state of the tracker, for debugging.
+    ///
+    /// The snapshot records the accumulated pointstamps and implications at each location, the
+    /// changes not yet propagated, and the propagated changes not yet drained, each consolidated and
+    /// sorted. Snapshots taken before and after `propagate_all` reveal the effect of propagation.
+    pub fn snapshot(&self) -> TrackerSnapshot<T> {
+
+        let mut pointstamps = ChangeBatch::new();
+        let mut implications = ChangeBatch::new();
+        for (node, state) in self.per_operator.iter().enumerate() {
+            for (port, target) in state.targets.iter().enumerate() {
+                let location = Location::new_target(node, port);
+                pointstamps.extend(target.pointstamps.updates().map(|(time, diff)| ((location, time.clone()), *diff)));
+                implications.extend(target.implications.updates().map(|(time, diff)| ((location, time.clone()), *diff)));
+            }
+            for (port, source) in state.sources.iter().enumerate() {
+                let location = Location::new_source(node, port);
+                pointstamps.extend(source.pointstamps.updates().map(|(time, diff)| ((location, time.clone()), *diff)));
+                implications.extend(source.implications.updates().map(|(time, diff)| ((location, time.clone()), *diff)));
+            }
+        }
+
+        let mut pending = ChangeBatch::new();
+        pending.extend(self.target_changes.clone().into_inner().into_iter().map(|((target, time), diff)| ((Location::from(target), time), diff)));
+        pending.extend(self.source_changes.clone().into_inner().into_iter().map(|((source, time), diff)| ((Location::from(source), time), diff)));
+
+        let flatten = |batch: ChangeBatch<(Location, T)>| batch.into_inner().into_iter().map(|((location, time), diff)| (location, time, diff)).collect();
+
+        TrackerSnapshot {
+            pointstamps: flatten(pointstamps),
+            implications: flatten(implications),
+            pending: flatten(pending),
+            pushed: flatten(self.pushed_changes.clone()),
+            pushed_output: self.output_changes.iter().map(|changes| changes.clone().into_inner()).collect(),
+        }
+    }
+
     /// Indicates if pointstamp is in the scope-wide frontier.
     ///
     /// Such a pointstamp would, if removed from `self.pointstamps`, cause a change
@@ -666,6 +1160,23 @@ impl<T:Timestamp> Tracker<T> {
     }
 }
 
+/// A copy of the progress state of a `Tracker`, produced by `Tracker::snapshot`.
+///
+/// Each list is consolidated and sorted, so that snapshots may be compared directly.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrackerSnapshot<T> {
+    /// Accumulated pointstamp counts at each location.
+    pub pointstamps: Vec<(Location, T, i64)>,
+    /// Accumulated implications of the pointstamps at each location.
+    pub implications: Vec<(Location, T, i64)>,
+    /// Changes to pointstamps not yet propagated.
+    pub pending: Vec<(Location, T, i64)>,
+    /// Propagated changes to implications not yet drained through `pushed`.
+    pub pushed: Vec<(Location, T, i64)>,
+    /// Propagated changes at each scope output not yet drained through `pushed_output`.
+    pub pushed_output: Vec<Vec<(T, i64)>>,
+}
+
 /// Determines summaries from locations to scope outputs.
 ///
 /// Specifically, for each location whose node identifier is non-zero, we compile
@@ -678,21 +1189,24 @@ fn summarize_outputs<T: Timestamp>(
     edges: &Vec<Vec<Vec<Target>>>,
     ) -> HashMap<Location, Vec<Antichain<T::Summary>>>
 {
-    // A reverse edge map, to allow us to walk back up the dataflow graph.
-    let mut reverse = HashMap::new();
-    for (node, outputs) in edges.iter().enumerate() {
-        for (output, targets) in outputs.iter().enumerate() {
-            for target in targets.iter() {
-                reverse.insert(
-                    Location::from(*target),
-                    Location { node, port: Port::Source(output) }
-                );
-            }
-        }
-    }
+    summarize_outputs_counted::<T>(nodes, edges, None).0
+}
+
+/// Determines summaries from locations to scope outputs, and the rounds and changes taken.
+///
+/// The number of rounds is the greatest number of operators crossed by an extended summary,
+/// and the number of changes is the number of summaries added to any antichain. Each added
+/// summary is recorded in `trace`, if supplied.
+fn summarize_outputs_counted<T: Timestamp>(
+    nodes: &Vec<Vec<Vec<Antichain<T::Summary>>>>,
+    edges: &Vec<Vec<Vec<Target>>>,
+    trace: Option<&mut Vec<(Location, usize, T::Summary)>>,
+    ) -> (OutputSummaries<T>, (usize, usize))
+{
+    let reverse = reverse_edges(edges);
 
     let mut results = HashMap::new();
-    let mut worklist = VecDeque::<(Location, usize, T::Summary)>::new();
+    let mut worklist = VecDeque::<(Location, usize, T::Summary, usize)>::new();
 
     let outputs =
     edges
@@ -703,11 +1217,52 @@ fn summarize_outputs<T: Timestamp>(
 
     // The scope may have no outputs, in which case we can do no work.
     for output_target in outputs {
-        worklist.push_back((Location::from(*output_target), output_target.port, Default::default()));
+        worklist.push_back((Location::from(*output_target), output_target.port, Default::default(), 0));
+    }
+
+    let stats = extend_summaries::<T>(nodes, &reverse, &mut results, worklist, trace);
+    (results, stats)
+}
+
+/// A reverse edge map, to allow us to walk back up the dataflow graph.
+///
+/// A target may be fed by several sources, each of which is recorded.
+fn reverse_edges(edges: &[Vec<Vec<Target>>]) -> HashMap<Location, Vec<Location>> {
+    let mut reverse = HashMap::new();
+    for (node, outputs) in edges.iter().enumerate() {
+        for (output, targets) in outputs.iter().enumerate() {
+            for target in targets.iter() {
+                reverse
+                    .entry(Location::from(*target))
+                    .or_insert_with(Vec::new)
+                    .push(Location { node, port: Port::Source(output) });
+            }
+        }
     }
+    reverse
+}
+
+/// Extends `results` with the summaries implied by those in `worklist`, walking back up the graph.
+///
+/// Each element of the worklist is a location, a scope output, a summary from the location to the
+/// output that has been added to `results`, and the number of operators crossed to reach it. The
+/// result is the number of rounds and of changes, as for `summarize_outputs_counted`. Summaries
+/// added to `results` are also recorded, in the order added, in `trace` if it is supplied.
+fn extend_summaries<T: Timestamp>(
+    nodes: &[Vec<Vec<Antichain<T::Summary>>>],
+    reverse: &HashMap<Location, Vec<Location>>,
+    results: &mut OutputSummaries<T>,
+    mut worklist: VecDeque<(Location, usize, T::Summary, usize)>,
+    mut trace: Option<&mut Vec<(Location, usize, T::Summary)>>,
+    ) -> (usize, usize)
+{
+    let mut rounds = 0;
+    let mut changes = 0;
 
     // Loop until we stop discovering novel reachability paths.
-    while let Some((location, output, summary)) = worklist.pop_front() {
+    while let Some((location, output, summary, depth)) = worklist.pop_front() {
+
+        rounds = ::std::cmp::max(rounds, depth);
 
         match location.port {
 
@@ -720,14 +1275,18 @@ fn summarize_outputs<T: Timestamp>(
 
                     // Determine the current path summaries from the input port.
                     let location = Location { node: location.node, port: Port::Target(input_port) };
-                    let antichains = results.entry(location).or_insert(Vec::new());
+                    let antichains = results.entry(location).or_default();
                     while antichains.len() <= output { antichains.push(Antichain::new()); }
 
                     // Combine each operator-internal summary to the output with `summary`.
                     for operator_summary in summaries[output_port].elements().iter() {
                         if let Some(combined) = operator_summary.followed_by(&summary) {
                             if antichains[output].insert(combined.clone()) {
-                                worklist.push_back((location, output, combined));
+                                changes += 1;
+                                if let Some(trace) = trace.as_mut() {
+                                    trace.push((location, output, combined.clone()));
+                                }
+                                worklist.push_back((location, output, combined, depth + 1));
                             }
                         }
                     }
@@ -739,13 +1298,17 @@ fn summarize_outputs<T: Timestamp>(
             // We want to walk back the edges leading to it.
             Port::Target(_port) => {
 
-                // Each target should have (at most) one source.
-                if let Some(source) = reverse.get(&location) {
-                    let antichains = results.entry(*source).or_insert(Vec::new());
+                // Each source feeding the target reaches the output with the same summary.
+                for source in reverse.get(&location).into_iter().flatten() {
+                    let antichains = results.entry(*source).or_default();
                     while antichains.len() <= output { antichains.push(Antichain::new()); }
 
                     if antichains[output].insert(summary.clone()) {
-                        worklist.push_back((*source, output, summary.clone()));
+                        changes += 1;
+                        if let Some(trace) = trace.as_mut() {
+                            trace.push((*source, output, summary.clone()));
+                        }
+                        worklist.push_back((*source, output, summary.clone(), depth));
                     }
                 }
 
@@ -754,5 +1317,574 @@ fn summarize_outputs<T: Timestamp>(
 
     }
 
-    results
+    (rounds, changes)
+}
+
+/// Tests whether two collections of output summaries describe the same reachability.
+///
+/// Missing locations and missing trailing outputs are treated as empty antichains, and
+/// antichains are compared as sets rather than as sequences.
+fn equivalent_summaries<T: Timestamp>(
+    summaries1: &OutputSummaries<T>,
+    summaries2: &OutputSummaries<T>,
+    ) -> bool
+{
+    let empty = Vec::new();
+    summaries1.keys().chain(summaries2.keys()).all(|location| {
+        let antichains1 = summaries1.get(location).unwrap_or(&empty);
+        let antichains2 = summaries2.get(location).unwrap_or(&empty);
+        let outputs = ::std::cmp::max(antichains1.len(), antichains2.len());
+        (0 .. outputs).all(|output| {
+            let none = Antichain::new();
+            let antichain1 = antichains1.get(output).unwrap_or(&none);
+            let antichain2 = antichains2.get(output).unwrap_or(&none);
+            antichain1.dominates(antichain2) && antichain2.dominates(antichain1)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::order::{Product, Product3};
+    use crate::progress::{Location, Source, Target};
+    use crate::progress::frontier::Antichain;
+
+    use super::Builder;
+
+    #[test]
+    fn incomparable_summaries_retained() {
+
+        let mut builder = Builder::<Product<usize, usize>>::new();
+
+        // The scope, an operator advancing different coordinates on each of two paths, and an
+        // operator that merges the paths back together.
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        builder.add_node(1, 1, 2, vec![vec![
+            Antichain::from_elem(Product::new(1, 0)),
+            Antichain::from_elem(Product::new(0, 1)),
+        ]]);
+        builder.add_node(2, 2, 1, vec![
+            vec![Antichain::from_elem(Default::default())],
+            vec![Antichain::from_elem(Default::default())],
+        ]);
+
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(2, 0));
+        builder.add_edge(Source::new(1, 1), Target::new(2, 1));
+        builder.add_edge(Source::new(2, 0), Target::new(0, 0));
+
+        let (mut tracker, scope_summary) = builder.build();
+
+        // Both paths from the scope input to the scope output must be retained.
+        let mut summaries = scope_summary[0][0].elements().to_vec();
+        summaries.sort();
+        assert_eq!(summaries, vec![Product::new(0, 1), Product::new(1, 0)]);
+
+        // A capability at the scope input reaches the scope output along both paths.
+        tracker.update_source(Source::new(0, 0), Product::new(0, 0), 1);
+        tracker.propagate_all();
+
+        let mut results =
+        tracker
+            .pushed()
+            .drain()
+            .filter(|((location, _time), _delta)| location == &Location::new_target(0, 0))
+            .collect::<Vec<_>>();
+
+        results.sort();
+        assert_eq!(results, vec![
+            ((Location::new_target(0, 0), Product::new(0, 1)), 1),
+            ((Location::new_target(0, 0), Product::new(1, 0)), 1),
+        ]);
+    }
+
+    #[test]
+    fn triple_summaries_match_nested() {
+
+        type Nested = Product<Product<usize, usize>, usize>;
+        type Triple = Product3<usize, usize, usize>;
+
+        // A loop advancing the innermost coordinate, within one advancing the middle coordinate.
+        let summaries = [(0, 0, 1), (0, 1, 0)];
+
+        let mut nested = Builder::<Nested>::new();
+        let mut triple = Builder::<Triple>::new();
+        nested.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        triple.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        for (index, &(outer, middle, inner)) in summaries.iter().enumerate() {
+            nested.add_node(index + 1, 1, 1, vec![vec![Antichain::from_elem(Product::new(Product::new(outer, middle), inner))]]);
+            triple.add_node(index + 1, 1, 1, vec![vec![Antichain::from_elem(Product3::new(outer, middle, inner))]]);
+        }
+        for (source, target) in [
+            (Source::new(0, 0), Target::new(1, 0)),
+            (Source::new(1, 0), Target::new(2, 0)),
+            (Source::new(2, 0), Target::new(1, 0)),
+            (Source::new(2, 0), Target::new(0, 0)),
+        ] {
+            nested.add_edge(source, target);
+            triple.add_edge(source, target);
+        }
+
+        let flatten = |antichain: &Antichain<Nested>| {
+            let mut elements = antichain.elements().iter()
+                .map(|summary| Product3::new(summary.outer.outer, summary.outer.inner, summary.inner))
+                .collect::<Vec<_>>();
+            elements.sort();
+            elements
+        };
+        let sorted = |antichain: &Antichain<Triple>| {
+            let mut elements = antichain.elements().to_vec();
+            elements.sort();
+            elements
+        };
+
+        let nested_summaries = nested.summarize();
+        let triple_summaries = triple.summarize();
+        assert_eq!(nested_summaries.len(), triple_summaries.len());
+
+        // The input of the outer operator is fed both by the scope input and by the inner operator.
+        assert_eq!(sorted(&triple_summaries[&Location::new_source(0, 0)][0]), vec![Product3::new(0, 1, 1)]);
+        assert_eq!(sorted(&triple_summaries[&Location::new_source(2, 0)][0]), vec![Product3::new(0, 0, 0)]);
+        for (location, antichains) in nested_summaries.iter() {
+            assert_eq!(
+                antichains.iter().map(flatten).collect::<Vec<_>>(),
+                triple_summaries[location].iter().map(sorted).collect::<Vec<_>>(),
+            );
+        }
+
+        let (_, nested_scope) = nested.build();
+        let (_, triple_scope) = triple.build();
+        assert_eq!(sorted(&triple_scope[0][0]), vec![Product3::new(0, 1, 1)]);
+        assert_eq!(flatten(&nested_scope[0][0]), sorted(&triple_scope[0][0]));
+    }
+
+    #[test]
+    fn summarize_traced_chain() {
+
+        let mut builder = Builder::<usize>::new();
+
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+        builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(2)]]);
+
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(2, 0));
+        builder.add_edge(Source::new(2, 0), Target::new(0, 0));
+
+        let mut trace = Vec::new();
+        let summaries = builder.summarize_traced(&mut trace);
+        assert_eq!(summaries, builder.summarize());
+
+        // Summaries are extended back from the scope output, composing the operators' summaries.
+        assert_eq!(trace, vec![
+            (Location::new_source(2, 0), 0, 0),
+            (Location::new_target(2, 0), 0, 2),
+            (Location::new_source(1, 0), 0, 2),
+            (Location::new_target(1, 0), 0, 3),
+            (Location::new_source(0, 0), 0, 3),
+        ]);
+    }
+
+    #[test]
+    fn meet_summaries_collapse() {
+
+        let mut builder = Builder::<Product<usize, usize>>::new();
+
+        // As in `incomparable_summaries_retained`, two paths advancing different coordinates.
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        builder.add_node(1, 1, 2, vec![vec![
+            Antichain::from_elem(Product::new(1, 0)),
+            Antichain::from_elem(Product::new(0, 1)),
+        ]]);
+        builder.add_node(2, 2, 1, vec![
+            vec![Antichain::from_elem(Default::default())],
+            vec![Antichain::from_elem(Default::default())],
+        ]);
+
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(2, 0));
+        builder.add_edge(Source::new(1, 1), Target::new(2, 1));
+        builder.add_edge(Source::new(2, 0), Target::new(0, 0));
+
+        let meet = |a: &Product<usize, usize>, b: &Product<usize, usize>| {
+            Product::new(::std::cmp::min(a.outer, b.outer), ::std::cmp::min(a.inner, b.inner))
+        };
+        let (mut full, _) = builder.build();
+        let (mut collapsed, scope_summary) = builder.build_with_meet(meet);
+
+        // The two summaries from the scope input to the output collapse to their meet.
+        assert_eq!(scope_summary[0][0].elements(), &[Product::new(0, 0)]);
+        assert_eq!(collapsed.node_state(1).targets[0].output_summaries[0].elements(), &[Product::new(0, 0)]);
+
+        // A message at the first operator is projected to one time no later than either full result.
+        full.update_target(Target::new(1, 0), Product::new(2, 2), 1);
+        collapsed.update_target(Target::new(1, 0), Product::new(2, 2), 1);
+        full.propagate_all();
+        collapsed.propagate_all();
+
+        let mut full_output = full.pushed_output()[0].drain().collect::<Vec<_>>();
+        full_output.sort();
+        assert_eq!(full_output, vec![(Product::new(2, 3), 1), (Product::new(3, 2), 1)]);
+        let collapsed_output = collapsed.pushed_output()[0].drain().collect::<Vec<_>>();
+        assert_eq!(collapsed_output, vec![(Product::new(2, 2), 1)]);
+
+        // Operator input frontiers are unaffected.
+        let mut full_pushed = full.pushed().drain().collect::<Vec<_>>();
+        let mut collapsed_pushed = collapsed.pushed().drain().collect::<Vec<_>>();
+        full_pushed.sort();
+        collapsed_pushed.sort();
+        assert_eq!(full_pushed, collapsed_pushed);
+    }
+
+    #[test]
+    fn filtered_summaries() {
+
+        let mut builder = Builder::<usize>::new();
+
+        // The scope input feeds two operators, each leading to its own scope output.
+        builder.add_node(0, 2, 1, vec![vec![Antichain::new()], vec![Antichain::new()]]);
+        builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+        builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(2)]]);
+
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(0, 0), Target::new(2, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(0, 0));
+        builder.add_edge(Source::new(2, 0), Target::new(0, 1));
+
+        let full = builder.summarize();
+        let filtered = builder.summarize_filtered(|index| index != 2);
+
+        // Both outputs are reachable from the scope input, but only one avoids operator two.
+        let input = Location::new_source(0, 0);
+        assert_eq!(full[&input], vec![Antichain::from_elem(1), Antichain::from_elem(2)]);
+        assert_eq!(filtered[&input], vec![Antichain::from_elem(1)]);
+
+        // Paths through operator one are unchanged, and operator two is absent.
+        assert_eq!(full[&Location::new_target(1, 0)], filtered[&Location::new_target(1, 0)]);
+        assert!(full.contains_key(&Location::new_target(2, 0)));
+        assert!(!filtered.contains_key(&Location::new_target(2, 0)));
+
+        // An accepting predicate reproduces the full summaries.
+        assert!(super::equivalent_summaries::<usize>(&full, &builder.summarize_filtered(|_| true)));
+    }
+
+    #[test]
+    fn compact_summaries_equivalent() {
+
+        let mut builder = Builder::<usize>::new();
+
+        // A chain of operators from the scope input to the first of two scope outputs.
+        builder.add_node(0, 2, 1, vec![vec![Antichain::new()], vec![Antichain::new()]]);
+        for index in 1 .. 5 {
+            builder.add_node(index, 1, 1, vec![vec![Antichain::from_elem(index)]]);
+            builder.add_edge(Source::new(index - 1, 0), Target::new(index, 0));
+        }
+        builder.add_edge(Source::new(4, 0), Target::new(0, 0));
+
+        let summaries = |tracker: &super::Tracker<usize>| {
+            (1 .. 5).map(|index| {
+                let state = tracker.node_state(index);
+                (state.targets[0].output_summaries.clone(), state.sources[0].output_summaries.clone())
+            })
+            .collect::<Vec<_>>()
+        };
+        let capacity = |tracker: &super::Tracker<usize>| {
+            (1 .. 5).map(|index| {
+                let state = tracker.node_state(index);
+                state.targets[0].output_summaries.capacity() + state.sources[0].output_summaries.capacity()
+            })
+            .sum::<usize>()
+        };
+
+        let (mut tracker, _) = builder.build();
+        let before = (summaries(&tracker), capacity(&tracker));
+        tracker.compact_summaries();
+        let after = (summaries(&tracker), capacity(&tracker));
+
+        assert_eq!(before.0, after.0);
+        assert!(after.1 < before.1);
+        assert_eq!(after.1, 8);
+
+        // The compacted tracker still projects capabilities to the scope output.
+        tracker.update_source(Source::new(2, 0), 5, 1);
+        tracker.propagate_all();
+        assert_eq!(tracker.pushed_output()[0].drain().collect::<Vec<_>>(), vec![(12, 1)]);
+    }
+
+    #[test]
+    fn installed_target_summaries() {
+
+        let mut builder = Builder::<usize>::new();
+
+        // Two operators in sequence from the scope input to the scope output.
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+        builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(2, 0));
+        builder.add_edge(Source::new(2, 0), Target::new(0, 0));
+
+        let (mut tracker, _) = builder.build();
+        tracker.install_target_summaries(vec![
+            vec![vec![Antichain::new()]],
+            vec![vec![Antichain::from_elem(10)]],
+            vec![vec![Antichain::from_elem(5)]],
+        ]);
+
+        // Messages are projected with the installed latencies, rather than the derived ones.
+        tracker.update_target(Target::new(1, 0), 3, 1);
+        tracker.update_target(Target::new(2, 0), 4, 1);
+        tracker.propagate_all();
+        let mut pushed = tracker.pushed_output()[0].drain().collect::<Vec<_>>();
+        pushed.sort();
+        assert_eq!(pushed, vec![(9, 1), (13, 1)]);
+
+        // Frontiers propagated between operators still follow the operators' summaries.
+        assert_eq!(tracker.node_state(2).targets[0].implications.frontier().to_vec(), vec![4]);
+    }
+
+    #[test]
+    fn update_summaries_matches_rebuild() {
+
+        let mut builder = Builder::<usize>::new();
+
+        // The scope input leads through one to three, and to the first scope output, and also
+        // through one to four, which loops back to itself through five.
+        builder.add_node(0, 2, 1, vec![vec![Antichain::new()], vec![Antichain::new()]]);
+        builder.add_node(1, 1, 2, vec![vec![Antichain::from_elem(1), Antichain::from_elem(2)]]);
+        builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(3)]]);
+        builder.add_node(3, 1, 1, vec![vec![Antichain::from_elem(4)]]);
+        builder.add_node(4, 2, 1, vec![vec![Antichain::from_elem(0)], vec![Antichain::from_elem(0)]]);
+        builder.add_node(5, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(2, 0));
+        builder.add_edge(Source::new(2, 0), Target::new(3, 0));
+        builder.add_edge(Source::new(3, 0), Target::new(0, 0));
+        builder.add_edge(Source::new(1, 1), Target::new(4, 0));
+        builder.add_edge(Source::new(4, 0), Target::new(5, 0));
+        builder.add_edge(Source::new(5, 0), Target::new(4, 1));
+
+        let mut summaries = builder.summarize();
+
+        // The internal summary of two changes, as if for edits within a nested subgraph.
+        builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(7)]]);
+        builder.update_summaries(&mut summaries, 2);
+        assert!(super::equivalent_summaries::<usize>(&summaries, &builder.summarize()));
+        assert_eq!(summaries[&Location::new_target(1, 0)], vec![Antichain::from_elem(12)]);
+
+        // The loop through four and five gains an edge to the second scope output.
+        builder.add_edge(Source::new(5, 0), Target::new(0, 1));
+        builder.update_summaries(&mut summaries, 5);
+        assert!(super::equivalent_summaries::<usize>(&summaries, &builder.summarize()));
+        assert_eq!(summaries[&Location::new_target(1, 0)], vec![Antichain::from_elem(12), Antichain::from_elem(3)]);
+    }
+
+    #[test]
+    fn summarize_fan_in() {
+
+        let mut builder = Builder::<usize>::new();
+
+        // Both scope inputs lead through their own operator into the one input of a third.
+        builder.add_node(0, 1, 2, vec![vec![Antichain::new(), Antichain::new()]]);
+        builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+        builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(2)]]);
+        builder.add_node(3, 1, 1, vec![vec![Antichain::from_elem(3)]]);
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(0, 1), Target::new(2, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(3, 0));
+        builder.add_edge(Source::new(2, 0), Target::new(3, 0));
+        builder.add_edge(Source::new(3, 0), Target::new(0, 0));
+
+        // Each source feeding the target of three reaches the scope output.
+        let summaries = builder.summarize();
+        assert_eq!(summaries[&Location::new_source(1, 0)], vec![Antichain::from_elem(3)]);
+        assert_eq!(summaries[&Location::new_source(2, 0)], vec![Antichain::from_elem(3)]);
+
+        let (_tracker, scope_summary) = builder.build();
+        assert_eq!(scope_summary, vec![vec![Antichain::from_elem(4)], vec![Antichain::from_elem(5)]]);
+    }
+
+    #[test]
+    fn update_summaries_fan_in() {
+
+        let mut builder = Builder::<usize>::new();
+
+        // Each scope input leads through its own operator, one or two, into the one input of three.
+        builder.add_node(0, 1, 2, vec![vec![Antichain::new(), Antichain::new()]]);
+        builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+        builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(2)]]);
+        builder.add_node(3, 1, 1, vec![vec![Antichain::from_elem(3)]]);
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(0, 1), Target::new(2, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(3, 0));
+        builder.add_edge(Source::new(2, 0), Target::new(3, 0));
+        builder.add_edge(Source::new(3, 0), Target::new(0, 0));
+
+        let mut summaries = builder.summarize();
+        assert_eq!(summaries[&Location::new_target(1, 0)], vec![Antichain::from_elem(4)]);
+        assert_eq!(summaries[&Location::new_target(2, 0)], vec![Antichain::from_elem(5)]);
+
+        // Both operators feeding three are upstream of it, and both must be updated.
+        builder.add_node(3, 1, 1, vec![vec![Antichain::from_elem(10)]]);
+        builder.update_summaries(&mut summaries, 3);
+        assert!(super::equivalent_summaries::<usize>(&summaries, &builder.summarize()));
+        assert_eq!(summaries[&Location::new_target(1, 0)], vec![Antichain::from_elem(11)]);
+        assert_eq!(summaries[&Location::new_target(2, 0)], vec![Antichain::from_elem(12)]);
+    }
+
+    #[test]
+    fn wide_frontier_changes() {
+
+        let mut builder = Builder::<Product<usize, usize>>::new();
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        builder.add_node(1, 1, 0, vec![vec![]]);
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        let (mut tracker, _) = builder.build();
+
+        // A frontier of many incomparable elements at the scope input.
+        for index in 0 .. 100 {
+            tracker.update_source(Source::new(0, 0), Product::new(index, 100 - index), 1);
+        }
+        tracker.propagate_all();
+        assert_eq!(tracker.pushed().drain().filter(|((l, _), _)| l.node == 1).count(), 100);
+
+        // Advancing one element, here to a time its neighbour dominates, changes only that element.
+        tracker.update_source(Source::new(0, 0), Product::new(50, 50), -1);
+        tracker.update_source(Source::new(0, 0), Product::new(50, 51), 1);
+        tracker.propagate_all();
+        let changes = tracker.pushed().drain().filter(|((l, _), _)| l.node == 1).collect::<Vec<_>>();
+        assert_eq!(changes, vec![((Location::new_target(1, 0), Product::new(50, 50)), -1)]);
+        assert_eq!(tracker.node_state(1).targets[0].implications.frontier().len(), 99);
+    }
+
+    #[test]
+    fn snapshot_propagation() {
+
+        let mut builder = Builder::<usize>::new();
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        builder.add_node(1, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(0, 0));
+        let (mut tracker, _) = builder.build();
+
+        tracker.update_source(Source::new(0, 0), 3, 1);
+        let before = tracker.snapshot();
+        assert_eq!(before.pending, vec![(Location::new_source(0, 0), 3, 1)]);
+        assert!(before.pointstamps.is_empty());
+        assert!(before.implications.is_empty());
+
+        tracker.propagate_all();
+        let after = tracker.snapshot();
+        assert!(after.pending.is_empty());
+        assert_eq!(after.pointstamps, vec![(Location::new_source(0, 0), 3, 1)]);
+
+        // The implications introduced by propagation, which remain to be drained.
+        let introduced = after.implications.iter().filter(|x| !before.implications.contains(x)).cloned().collect::<Vec<_>>();
+        assert_eq!(introduced, vec![
+            (Location::new_target(0, 0), 4, 1),
+            (Location::new_source(0, 0), 3, 1),
+            (Location::new_target(1, 0), 3, 1),
+            (Location::new_source(1, 0), 4, 1),
+        ]);
+        assert_eq!(after.pushed, introduced);
+
+        // Snapshots are copies, and observing the state does not change it.
+        assert_eq!(tracker.snapshot(), after);
+        tracker.pushed().drain();
+        assert!(tracker.snapshot().pushed.is_empty());
+    }
+
+    #[test]
+    fn summary_stats_chain_depth() {
+
+        let mut builder = Builder::<usize>::new();
+
+        // A chain of five operators from the scope input to the scope output.
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        for index in 1 .. 6 {
+            builder.add_node(index, 1, 1, vec![vec![Antichain::from_elem(1)]]);
+            builder.add_edge(Source::new(index - 1, 0), Target::new(index, 0));
+        }
+        builder.add_edge(Source::new(5, 0), Target::new(0, 0));
+
+        // Each operator adds a summary at its input, and at the output leading to it.
+        let (tracker, _) = builder.build();
+        assert_eq!(tracker.summary_stats(), (5, 11));
+    }
+
+    #[test]
+    fn looping_summaries_terminate() {
+
+        let mut builder = Builder::<Product<usize, usize>>::new();
+
+        // The scope input enters a loop whose feedback increments the inner coordinate, and
+        // whose body also leads to the scope output.
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        builder.add_node(1, 2, 1, vec![
+            vec![Antichain::from_elem(Default::default())],
+            vec![Antichain::from_elem(Default::default())],
+        ]);
+        builder.add_node(2, 1, 2, vec![vec![
+            Antichain::from_elem(Default::default()),
+            Antichain::from_elem(Default::default()),
+        ]]);
+        builder.add_node(3, 1, 1, vec![vec![Antichain::from_elem(Product::new(0, 1))]]);
+
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(2, 0));
+        builder.add_edge(Source::new(2, 0), Target::new(3, 0));
+        builder.add_edge(Source::new(3, 0), Target::new(1, 1));
+        builder.add_edge(Source::new(2, 1), Target::new(0, 0));
+
+        assert!(builder.is_acyclic());
+
+        // Every number of iterations reaches the output, which the least summary represents.
+        let (mut tracker, scope_summary) = builder.build();
+        assert_eq!(scope_summary[0][0], Antichain::from_elem(Default::default()));
+        assert_eq!(tracker.node_state(3).sources[0].output_summaries, vec![Antichain::from_elem(Default::default())]);
+
+        // A capability in the feedback reaches the output at all later iterations.
+        tracker.update_source(Source::new(3, 0), Product::new(0, 4), 1);
+        tracker.propagate_all();
+        assert_eq!(tracker.pushed_output()[0].drain().collect::<Vec<_>>(), vec![(Product::new(0, 4), 1)]);
+    }
+
+    #[test]
+    fn multiple_summaries_propagate() {
+
+        let mut builder = Builder::<Product<usize, usize>>::new();
+
+        // The scope, a connection that advances either the outer coordinate by one or the inner
+        // coordinate by two, and a downstream operator.
+        builder.add_node(0, 1, 1, vec![vec![Antichain::new()]]);
+        let mut branches = Antichain::new();
+        branches.insert(Product::new(1, 0));
+        branches.insert(Product::new(0, 2));
+        builder.add_node(1, 1, 1, vec![vec![branches]]);
+        builder.add_node(2, 1, 1, vec![vec![Antichain::from_elem(Default::default())]]);
+
+        builder.add_edge(Source::new(0, 0), Target::new(1, 0));
+        builder.add_edge(Source::new(1, 0), Target::new(2, 0));
+        builder.add_edge(Source::new(2, 0), Target::new(0, 0));
+
+        let (mut tracker, _) = builder.build();
+
+        tracker.update_source(Source::new(0, 0), Product::new(3, 3), 1);
+        tracker.propagate_all();
+
+        // Both branches reach the downstream operator.
+        let mut results =
+        tracker
+            .pushed()
+            .drain()
+            .filter(|((location, _time), _delta)| location == &Location::new_target(2, 0))
+            .collect::<Vec<_>>();
+
+        results.sort();
+        assert_eq!(results, vec![
+            ((Location::new_target(2, 0), Product::new(3, 5)), 1),
+            ((Location::new_target(2, 0), Product::new(4, 3)), 1),
+        ]);
+    }
 }
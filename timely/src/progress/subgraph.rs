@@ -7,22 +7,25 @@
 
 use std::rc::Rc;
 use std::cell::RefCell;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, BTreeMap, HashMap, VecDeque};
+use std::collections::hash_map::Entry;
 use std::cmp::Reverse;
+use std::convert::{TryFrom, TryInto};
 
 use crate::logging::TimelyLogger as Logger;
 
 use crate::scheduling::Schedule;
-use crate::scheduling::activate::Activations;
+use crate::scheduling::activate::{Activations, Activator};
 
-use crate::progress::frontier::{Antichain, MutableAntichain, MutableAntichainFilter};
-use crate::progress::{Timestamp, Operate, operate::SharedProgress};
+use crate::progress::frontier::{Antichain, AntichainRef, MutableAntichain, MutableAntichainFilter};
+use crate::progress::{Timestamp, Operate, operate::{SharedProgress, ProgressReport, summary_justifies}};
 use crate::progress::{Location, Port, Source, Target};
 
 use crate::progress::ChangeBatch;
 use crate::progress::broadcast::Progcaster;
 use crate::progress::reachability;
-use crate::progress::timestamp::Refines;
+use crate::progress::timestamp::{PathSummary, Refines};
+use crate::order::{PartialOrder, TotalOrder};
 
 // IMPORTANT : by convention, a child identifier of zero is used to indicate inputs and outputs of
 // the Subgraph itself. An identifier greater than zero corresponds to an actual child, which can
@@ -59,6 +62,9 @@ where
     // expressed capabilities, used to filter changes against.
     output_capabilities: Vec<MutableAntichain<TOuter>>,
 
+    // indicates whether the summaries of the built tracker are compacted.
+    compact_summaries: bool,
+
     /// Logging handle
     logging: Option<Logger>,
 }
@@ -69,12 +75,33 @@ where
     TInner: Timestamp+Refines<TOuter>,
 {
     /// Allocates a new input to the subgraph and returns the target to that input in the outer graph.
+    ///
+    /// Inputs may be added at any point before `build`, which consumes the builder and sizes all
+    /// progress tracking state from the inputs and outputs allocated by then.
     pub fn new_input(&mut self, shared_counts: Rc<RefCell<ChangeBatch<TInner>>>) -> Target {
         self.input_messages.push(shared_counts);
         Target::new(self.index, self.input_messages.len() - 1)
     }
 
+    /// Allocates `streams` inputs for logical streams multiplexed over one channel.
+    ///
+    /// Each logical stream is a separate input to the subgraph, with its own frontier and its own
+    /// count of records entering the scope, so that a slow stream does not hold back the progress
+    /// of the others. The result lists the target of each input in the outer graph, with the counts
+    /// that whatever delivers the multiplexed records should update for the corresponding stream.
+    pub fn new_input_multiplexed(&mut self, streams: usize) -> Vec<(Target, Rc<RefCell<ChangeBatch<TInner>>>)> {
+        (0 .. streams)
+            .map(|_| {
+                let shared_counts = Rc::new(RefCell::new(ChangeBatch::new()));
+                (self.new_input(shared_counts.clone()), shared_counts)
+            })
+            .collect()
+    }
+
     /// Allocates a new output from the subgraph and returns the source of that output in the outer graph.
+    ///
+    /// As with inputs, outputs may be added at any point before `build`, including after children
+    /// and edges have been added; once built, the subgraph's shape is fixed.
     pub fn new_output(&mut self) -> Source {
         self.output_capabilities.push(MutableAntichain::new());
         Source::new(self.index, self.output_capabilities.len() - 1)
@@ -111,10 +138,20 @@ where
             edge_stash: Vec::new(),
             input_messages: Vec::new(),
             output_capabilities: Vec::new(),
+            compact_summaries: false,
             logging,
         }
     }
 
+    /// Sets whether the progress tracker of the built subgraph compacts its summaries.
+    ///
+    /// Compaction releases the capacity left over from computing the summaries, and discards those
+    /// that are empty, which suits subgraphs that are built once and run for a long time. It is
+    /// initially disabled.
+    pub fn compact_summaries(&mut self, compact: bool) {
+        self.compact_summaries = compact;
+    }
+
     /// Allocates a new child identifier, for later use.
     pub fn allocate_child_id(&mut self) -> usize {
         self.child_count += 1;
@@ -122,6 +159,10 @@ where
     }
 
     /// Adds a new child to the subgraph.
+    ///
+    /// Children are operators on `TInner`, and are otherwise opaque. A child may itself be a
+    /// subgraph, with any inner timestamp that refines `TInner`, and sibling subgraphs need not
+    /// agree on their inner timestamps.
     pub fn add_child(&mut self, child: Box<dyn Operate<TInner>>, index: usize, identifier: usize) {
         {
             let mut child_path = self.path.clone();
@@ -165,7 +206,10 @@ where
             builder.add_edge(source, target);
         }
 
-        let (tracker, scope_summary) = builder.build();
+        let (mut tracker, scope_summary) = builder.build();
+        if self.compact_summaries {
+            tracker.compact_summaries();
+        }
 
         let progcaster = Progcaster::new(worker, &self.path, self.logging.clone());
 
@@ -173,6 +217,7 @@ where
         incomplete[0] = false;
         let incomplete_count = incomplete.len() - 1;
 
+        let peers = worker.peers();
         let activations = worker.activations().clone();
 
         activations.borrow_mut().activate(&self.path[..]);
@@ -184,21 +229,45 @@ where
             outputs,
             incomplete,
             incomplete_count,
+            ticks: 0,
+            paused: vec![false; self.children.len()],
+            gates: (0 .. self.children.len()).map(|_| None).collect(),
+            gate_frontier: Antichain::new(),
+            gate_sorted: Vec::new(),
             activations,
             temp_active: BinaryHeap::new(),
             children: self.children,
             input_messages: self.input_messages,
             output_capabilities: self.output_capabilities,
+            reported_capabilities: (0 .. outputs).map(|_| MutableAntichain::new()).collect(),
+            output_floors: vec![None; outputs],
+            reorder_windows: (0 .. outputs).map(|_| None).collect(),
+            input_epoch: None,
+            output_holds: (0 .. outputs).map(|_| Rc::new(RefCell::new(ChangeBatch::new()))).collect(),
+            probes: Vec::new(),
 
             local_pointstamp: ChangeBatch::new(),
             final_pointstamp: ChangeBatch::new(),
             progcaster,
+            peers,
             pointstamp_tracker: tracker,
 
             shared_progress: Rc::new(RefCell::new(SharedProgress::new(inputs, outputs))),
             scope_summary,
 
             eager_progress_send: ::std::env::var("DEFAULT_PROGRESS_MODE") != Ok("DEMAND".to_owned()),
+
+            backpressure: Vec::new(),
+            frontier_quantum: None,
+            frontier_changes: None,
+            capability_ledger: None,
+            audit: false,
+            history_depth: 0,
+            frontier_history: VecDeque::new(),
+            edge_labels: BTreeMap::new(),
+
+            summarized: false,
+            sealed: false,
         }
     }
 }
@@ -208,6 +277,22 @@ where
 ///
 /// The subgraph type contains the infrastructure required to describe the topology of and track
 /// progress within a dataflow subgraph.
+///
+/// Progress tracking within a subgraph is deterministic for a single worker: active children are
+/// scheduled in order of their index, progress updates are consolidated and applied in sorted order,
+/// and path summaries are derived by a traversal in the order edges were added. Two runs with the
+/// same inputs and the same child behavior report identical progress. With multiple workers the
+/// interleaving of exchanged progress updates is not deterministic, though their net effect is.
+///
+/// A subgraph must be initialized by its parent, by calling `get_internal_summary` and then
+/// `set_external_summary`, before it is scheduled. Its progress information is meaningless
+/// without the initial capabilities and frontiers these establish, and the subgraph panics
+/// if it is scheduled, or receives its external summary, out of order.
+///
+/// The buffers through which a subgraph collects, exchanges, and applies progress updates are
+/// retained between schedulings, and are drained rather than reallocated, so that scheduling does
+/// not allocate once the buffers have grown to fit the typical batch of updates. After a burst of
+/// updates, `trim_buffers` releases the excess capacity.
 pub struct Subgraph<TOuter, TInner>
 where
     TOuter: Timestamp,
@@ -224,6 +309,13 @@ where
 
     incomplete: Vec<bool>,   // the incompletion status of each child.
     incomplete_count: usize, // the number of incomplete children.
+    ticks: usize,            // the number of times the subgraph has been scheduled.
+    paused: Vec<bool>,       // children not to be scheduled, for debugging.
+    // predicates on input frontiers that children must satisfy to be scheduled.
+    gates: Vec<Option<Gate<TOuter>>>,
+    // buffers for the input frontier of a gated child, retained between uses.
+    gate_frontier: Antichain<TOuter>,
+    gate_sorted: Vec<TOuter>,
 
     // shared activations (including children).
     activations: Rc<RefCell<Activations>>,
@@ -234,6 +326,18 @@ where
 
     // expressed capabilities, used to filter changes against.
     output_capabilities: Vec<MutableAntichain<TOuter>>,
+    // capabilities as reported to the parent, after rounding and flooring.
+    reported_capabilities: Vec<MutableAntichain<TOuter>>,
+    // least times reported as capabilities for each output.
+    output_floors: Vec<Option<TOuter>>,
+    // moves outer times back by a reordering window before they are reported, for each output.
+    reorder_windows: Vec<Option<Retime<TOuter>>>,
+    // the epoch through which inputs are sealed, before which input frontiers and messages are excluded.
+    input_epoch: Option<TOuter>,
+    // changes to capabilities held through `hold_output`, not yet applied.
+    output_holds: Vec<Rc<RefCell<ChangeBatch<TOuter>>>>,
+    // probes of several outputs, with the frontier last presented to each.
+    probes: Vec<(Vec<usize>, OutputProbe<TOuter>, Vec<TOuter>)>,
 
     // pointstamp messages to exchange. ultimately destined for `messages` or `internal`.
     local_pointstamp: ChangeBatch<(Location, TInner)>,
@@ -245,11 +349,39 @@ where
 
     // channel / whatever used to communicate pointstamp updates to peers.
     progcaster: Progcaster<TInner>,
+    // number of workers exchanging pointstamp updates, including this one.
+    peers: usize,
 
     shared_progress: Rc<RefCell<SharedProgress<TOuter>>>,
     scope_summary: Vec<Vec<Antichain<TInner::Summary>>>,
 
     eager_progress_send: bool,
+
+    // high watermarks for outstanding messages at child inputs.
+    backpressure: Vec<(Target, i64)>,
+
+    // rounds outer times down before they are reported as output capabilities.
+    frontier_quantum: Option<Box<dyn Fn(TOuter)->TOuter>>,
+
+    // changes to the input frontiers of each child during the last scheduling, if recorded.
+    frontier_changes: Option<Vec<Vec<(usize, TInner, i64)>>>,
+
+    // net capabilities acquired at the outputs of children, if tracked.
+    capability_ledger: Option<ChangeBatch<(Location, TInner)>>,
+
+    // indicates whether the progress children report is audited against their summaries.
+    audit: bool,
+
+    // reported output frontiers after each of the most recent `history_depth` schedulings.
+    history_depth: usize,
+    frontier_history: VecDeque<Vec<Vec<TOuter>>>,
+
+    // labels for edges, for presentation only.
+    edge_labels: BTreeMap<(Source, Target), String>,
+
+    // indicate whether `get_internal_summary` and `set_external_summary` have been called.
+    summarized: bool,
+    sealed: bool,
 }
 
 impl<TOuter, TInner> Schedule for Subgraph<TOuter, TInner>
@@ -268,6 +400,49 @@ where
         // into atomic actions that should be able to be safely executed in
         // isolation, by a potentially clueless user (yours truly).
 
+        self.begin_tick();              // Accept progress, and enqueue active children.
+
+        // Schedule child operators.
+        //
+        // We should be able to schedule arbitrary subsets of children, as
+        // long as we eventually schedule all children that need to do work.
+        let mut previous = 0;
+        while let Some(Reverse(index)) = self.temp_active.pop() {
+            // De-duplicate, and don't revisit. Paused children are resumed with an activation.
+            if index > previous {
+                if !self.paused[index] && self.gate_open(index) {
+                    self.activate_child(index);
+                }
+                previous = index;
+            }
+        }
+
+        self.end_tick()                 // Transmit progress, and report incompletion.
+    }
+}
+
+
+impl<TOuter, TInner> Subgraph<TOuter, TInner>
+where
+    TOuter: Timestamp,
+    TInner: Timestamp+Refines<TOuter>,
+{
+    /// Begins a scheduling, applying progress from the parent and from peers.
+    ///
+    /// The scheduling is counted, frontier changes and messages from the parent are accepted,
+    /// exchanged progress updates are received and propagated, and active children are enqueued.
+    fn begin_tick(&mut self) {
+
+        // Progress tracking relies on initial capabilities and frontiers, which the parent supplies.
+        if !self.sealed {
+            panic!("Subgraph {:?} scheduled before `get_internal_summary` and `set_external_summary`", self.name);
+        }
+
+        self.ticks += 1;
+        if let Some(changes) = self.frontier_changes.as_mut() {
+            changes.iter_mut().for_each(|changes| changes.clear());
+        }
+
         self.accept_frontier();         // Accept supplied frontier changes.
         self.harvest_inputs();          // Count records entering the scope.
 
@@ -283,19 +458,12 @@ where
                 .borrow_mut()
                 .for_extensions(&self.path[..], |index| temp_active.push(Reverse(index)));
         }
+    }
 
-        // Schedule child operators.
-        //
-        // We should be able to schedule arbitrary subsets of children, as
-        // long as we eventually schedule all children that need to do work.
-        let mut previous = 0;
-        while let Some(Reverse(index)) = self.temp_active.pop() {
-            // De-duplicate, and don't revisit.
-            if index > previous {
-                self.activate_child(index);
-                previous = index;
-            }
-        }
+    /// Completes a scheduling, sending progress and recording reported frontiers.
+    ///
+    /// The return value indicates that the subgraph cannot yet shut down.
+    fn end_tick(&mut self) -> bool {
 
         // Transmit produced progress updates.
         self.send_progress();
@@ -305,20 +473,23 @@ where
             self.activations.borrow_mut().activate(&self.path[..]);
         }
 
+        // Record reported output frontiers, if requested.
+        if self.history_depth > 0 {
+            if self.frontier_history.len() == self.history_depth {
+                self.frontier_history.pop_front();
+            }
+            let frontiers = self.reported_capabilities.iter().map(|caps| caps.frontier().to_vec()).collect();
+            self.frontier_history.push_back(frontiers);
+        }
+
         // A subgraph is incomplete if any child is incomplete, or there are outstanding messages.
         let incomplete = self.incomplete_count > 0;
         let tracking = self.pointstamp_tracker.tracking_anything();
+        let holding = self.output_capabilities.iter().any(|caps| !caps.is_empty());
 
-        incomplete || tracking
+        incomplete || tracking || holding
     }
-}
-
 
-impl<TOuter, TInner> Subgraph<TOuter, TInner>
-where
-    TOuter: Timestamp,
-    TInner: Timestamp+Refines<TOuter>,
-{
     /// Schedules a child operator and collects progress statements.
     ///
     /// The return value indicates that the child task cannot yet shut down.
@@ -350,6 +521,11 @@ where
             }
         }
 
+        // If requested, record progress not justified by the child's internal summary.
+        if self.audit {
+            child.audit_progress(self.pointstamp_tracker.node_state(child_index));
+        }
+
         // Extract progress statements into either pre- or post-exchange buffers.
         if child.local {
             child.extract_progress(&mut self.local_pointstamp, &mut self.temp_active);
@@ -368,7 +544,7 @@ where
             for (time, value) in changes.drain() {
                 self.pointstamp_tracker.update_source(
                     source,
-                    TInner::to_inner(time),
+                    seal_time(&self.input_epoch, TInner::to_inner(time)),
                     value
                 );
             }
@@ -386,6 +562,11 @@ where
             let source = Location::new_source(0, input);
             let mut borrowed = self.input_messages[input].borrow_mut();
             for (time, delta) in borrowed.drain() {
+                if let Some(epoch) = &self.input_epoch {
+                    if time.clone().to_outer().less_than(epoch) {
+                        panic!("Subgraph {:?} received messages at {:?} on input {}, before sealed epoch {:?}", self.name, time, input, epoch);
+                    }
+                }
                 for target in &self.children[0].edges[input] {
                     self.local_pointstamp.update((Location::from(*target), time.clone()), delta);
                 }
@@ -437,6 +618,9 @@ where
                 }
             }
             else {
+                if let (Some(ledger), Port::Source(_)) = (self.capability_ledger.as_mut(), location.port) {
+                    ledger.update((location, timestamp.clone()), delta);
+                }
                 self.pointstamp_tracker.update(location, timestamp, delta);
             }
         }
@@ -448,12 +632,15 @@ where
         for ((location, time), diff) in self.pointstamp_tracker.pushed().drain() {
             // Targets are actionable, sources are not.
             if let crate::progress::Port::Target(port) = location.port {
-                if self.children[location.node].notify {
+                if self.children[location.node].notify || self.gates[location.node].is_some() {
                     self.temp_active.push(Reverse(location.node));
                 }
                 // TODO: This logic could also be guarded by `.notify`, but
                 // we want to be a bit careful to make sure all related logic
                 // agrees with this (e.g. initialization, operator logic, etc.)
+                if let Some(changes) = self.frontier_changes.as_mut() {
+                    changes[location.node].push((port, time.clone(), diff));
+                }
                 self.children[location.node]
                     .shared_progress
                     .borrow_mut()
@@ -463,14 +650,148 @@ where
         }
 
         // Extract child zero frontier changes and report as internal capability changes.
+        let quantum = &self.frontier_quantum;
         for (output, internal) in self.shared_progress.borrow_mut().internals.iter_mut().enumerate() {
+            let floor = &self.output_floors[output];
+            let window = &self.reorder_windows[output];
+            let reported = &mut self.reported_capabilities[output];
             self.pointstamp_tracker
                 .pushed_output()[output]
                 .drain()
                 .map(|(time, diff)| (time.to_outer(), diff))
                 .filter_through(&mut self.output_capabilities[output])
+                .map(|(time, diff)| (report_time(quantum, window, floor, time), diff))
+                .filter_through(reported)
+                .for_each(|(time, diff)| internal.update(time, diff));
+
+            // Released holds are subject to the same filtering.
+            self.output_holds[output]
+                .borrow_mut()
+                .drain()
+                .filter_through(&mut self.output_capabilities[output])
+                .map(|(time, diff)| (report_time(quantum, window, floor, time), diff))
+                .filter_through(reported)
+                .for_each(|(time, diff)| internal.update(time, diff));
+        }
+
+        if !self.probes.is_empty() {
+            self.update_probes();
+        }
+    }
+
+    /// Presents the combined frontiers of the reported capabilities of their outputs to probes.
+    fn update_probes(&mut self) {
+        for (outputs, handle, frontier) in self.probes.iter_mut() {
+            let mut combined = Antichain::new();
+            for output in outputs.iter() {
+                combined.extend(self.reported_capabilities[*output].frontier().iter().cloned());
+            }
+            let combined = combined.elements().to_vec();
+            if combined != *frontier {
+                handle.frontier.borrow_mut().update_iter(
+                    frontier.drain(..).map(|time| (time, -1))
+                        .chain(combined.iter().cloned().map(|time| (time, 1)))
+                );
+                *frontier = combined;
+            }
+        }
+    }
+
+    /// A probe whose frontier combines the frontiers of several outputs.
+    ///
+    /// The probe's frontier is that of the capabilities reported to the parent on all of `outputs`,
+    /// and so a time is complete for the probe only once it is complete for each of `outputs`. The
+    /// probe is updated as the subgraph propagates progress, and may be cloned and inspected as a
+    /// `probe::Handle` is, but not updated.
+    pub fn combined_probe(&mut self, outputs: &[usize]) -> OutputProbe<TOuter> {
+        for output in outputs.iter() {
+            assert!(*output < self.outputs, "Invalid output {} to probe", output);
+        }
+        let handle = OutputProbe { frontier: Rc::new(RefCell::new(MutableAntichain::new())) };
+        self.probes.push((outputs.to_vec(), handle.clone(), Vec::new()));
+        self.update_probes();
+        handle
+    }
+
+    /// Changes how capabilities are reported, and re-expresses the reported capabilities.
+    fn change_reporting<F: FnOnce(&mut Self)>(&mut self, change: F) {
+        let frontiers = self.output_capabilities.iter().map(|caps| caps.frontier().to_vec()).collect::<Vec<_>>();
+        let mut changes = vec![ChangeBatch::new(); frontiers.len()];
+        for (output, frontier) in frontiers.iter().enumerate() {
+            let floor = &self.output_floors[output];
+            let window = &self.reorder_windows[output];
+            changes[output].extend(frontier.iter().map(|time| (report_time(&self.frontier_quantum, window, floor, time.clone()), -1)));
+        }
+        change(self);
+        let mut shared_progress = self.shared_progress.borrow_mut();
+        for (output, frontier) in frontiers.iter().enumerate() {
+            let floor = &self.output_floors[output];
+            let window = &self.reorder_windows[output];
+            changes[output].extend(frontier.iter().map(|time| (report_time(&self.frontier_quantum, window, floor, time.clone()), 1)));
+            let internal = &mut shared_progress.internals[output];
+            changes[output]
+                .drain()
+                .filter_through(&mut self.reported_capabilities[output])
                 .for_each(|(time, diff)| internal.update(time, diff));
         }
+        drop(shared_progress);
+        self.update_probes();
+    }
+
+    /// Reports capabilities on `output` at times no earlier than `floor`.
+    ///
+    /// A capability at a time less than `floor` is reported to the parent as a capability at
+    /// `floor`, so that the output frontier is never reported earlier than `floor`; times that
+    /// are greater or incomparable are reported unchanged. Capabilities within the subgraph are
+    /// tracked as before, and capabilities reported before the call are re-expressed. Unlike
+    /// other progress reporting, this allows the parent to observe an output frontier that has
+    /// advanced beyond times at which the subgraph may still produce output.
+    ///
+    /// Floors apply to the outputs of the subgraph, rather than to the children connected to them,
+    /// so a child output connected to several outputs is reported at each subject to its floor.
+    pub fn set_output_floor(&mut self, output: usize, floor: TOuter) {
+        self.change_reporting(|subgraph| subgraph.output_floors[output] = Some(floor));
+    }
+
+    /// Applies changes to the frontiers of all inputs, and propagates their consequences once.
+    ///
+    /// The changes in `per_input[input]` are applied to the frontier of `input` as if supplied by
+    /// the parent, and the consequences for the children's input frontiers are propagated together,
+    /// rather than once for each input. The outcome is the same as applying the changes to each input
+    /// in turn. Changes the parent has supplied but the subgraph has not yet accepted are applied too.
+    ///
+    /// This method panics if `per_input` has more entries than the subgraph has inputs.
+    pub fn push_external_progress_all(&mut self, per_input: Vec<Vec<(TOuter, i64)>>) {
+        assert!(per_input.len() <= self.inputs, "Changes for {} inputs supplied to {} inputs", per_input.len(), self.inputs);
+        {   // Scoped, to allow borrow to drop.
+            let mut shared_progress = self.shared_progress.borrow_mut();
+            for (input, changes) in per_input.into_iter().enumerate() {
+                shared_progress.frontiers[input].extend(changes.into_iter());
+            }
+        }
+        self.accept_frontier();
+        self.propagate_pointstamps();
+    }
+
+    /// The frontier of times still open at `input`, as accepted from the parent and sealed.
+    ///
+    /// Times not greater or equal to an element of the result are closed: the subgraph has been
+    /// assured it will receive no more messages at them on `input`, and a message at a time before a
+    /// sealed epoch would be rejected. The frontier reflects changes from the parent only once they
+    /// are accepted by scheduling the subgraph. The result is sorted, and is empty once `input` closes.
+    pub fn input_closed_through(&self, input: usize) -> Vec<TOuter> {
+        assert!(input < self.inputs, "Input {} out of range for {} inputs", input, self.inputs);
+        let mut frontier =
+        self.pointstamp_tracker
+            .node_state(0)
+            .sources[input]
+            .pointstamps
+            .frontier()
+            .iter()
+            .map(|time| time.clone().to_outer())
+            .collect::<Vec<_>>();
+        frontier.sort();
+        frontier
     }
 
     /// Sends local progress updates to all workers.
@@ -495,255 +816,3790 @@ where
             self.progcaster.send(&mut self.local_pointstamp);
         }
     }
-}
 
+    /// Schedules a single child and applies the progress it reports.
+    ///
+    /// Unlike `schedule`, this method neither accepts frontier changes from the parent scope nor
+    /// schedules any other child, though children affected by the reported progress are queued
+    /// to be scheduled by the next call to `schedule`. The return value indicates that the child
+    /// cannot yet shut down.
+    pub fn step_child(&mut self, child_index: usize) -> bool {
+        let incomplete = self.activate_child(child_index);
+        self.send_progress();
+        self.progcaster.recv(&mut self.final_pointstamp);
+        self.propagate_pointstamps();
+        incomplete
+    }
 
-impl<TOuter, TInner> Operate<TOuter> for Subgraph<TOuter, TInner>
-where
-    TOuter: Timestamp,
-    TInner: Timestamp+Refines<TOuter>,
-{
-    fn local(&self) -> bool { false }
-    fn inputs(&self)  -> usize { self.inputs }
-    fn outputs(&self) -> usize { self.outputs }
+    /// Exchanges all local progress updates, and applies those received.
+    ///
+    /// Unlike `send_progress`, this sends all of `self.local_pointstamp`.
+    fn flush_progress(&mut self) {
+        self.progcaster.send(&mut self.local_pointstamp);
+        self.progcaster.recv(&mut self.final_pointstamp);
+        self.propagate_pointstamps();
+    }
 
-    // produces connectivity summaries from inputs to outputs, and reports initial internal
-    // capabilities on each of the outputs (projecting capabilities from contained scopes).
-    fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<TOuter::Summary>>>, Rc<RefCell<SharedProgress<TOuter>>>) {
+    /// Stops scheduling a child, for example to inspect the rest of the graph while it is frozen.
+    ///
+    /// A paused child is not scheduled and its progress is not collected, so the messages sent to
+    /// it remain outstanding and the frontiers of operators downstream of it cannot advance past
+    /// them. Changes to its input frontiers are still delivered, and accumulate until it resumes.
+    pub fn pause_scope(&mut self, child_index: usize) {
+        assert!(child_index > 0 && child_index < self.children.len(), "Invalid child {} to pause", child_index);
+        self.paused[child_index] = true;
+    }
 
-        // double-check that child 0 (the outside world) is correctly shaped.
-        assert_eq!(self.children[0].outputs, self.inputs());
-        assert_eq!(self.children[0].inputs, self.outputs());
+    /// Overrides whether a child is activated by changes to its input frontiers.
+    ///
+    /// This replaces the value the child reported through `notify_me`. A child that is not
+    /// notified still has the changes applied to its input frontiers, but is not scheduled in
+    /// response to them, and will only observe them when otherwise activated. Turning
+    /// notification off for a child that awaits frontier changes may leave it idle indefinitely.
+    /// Path summaries do not depend on notification, and are unaffected.
+    pub fn set_scope_notify(&mut self, child_index: usize, notify: bool) {
+        assert!(child_index > 0 && child_index < self.children.len(), "Invalid child {} to notify", child_index);
+        self.children[child_index].notify = notify;
+    }
 
-        let mut internal_summary = vec![vec![Antichain::new(); self.outputs()]; self.inputs()];
-        for input in 0 .. self.scope_summary.len() {
-            for output in 0 .. self.scope_summary[input].len() {
-                for path_summary in self.scope_summary[input][output].elements().iter() {
-                    internal_summary[input][output].insert(TInner::summarize(path_summary.clone()));
-                }
-            }
+    /// Resumes scheduling a paused child, which is activated to process its backlog.
+    pub fn resume_scope(&mut self, child_index: usize) {
+        assert!(child_index > 0 && child_index < self.children.len(), "Invalid child {} to resume", child_index);
+        if self.paused[child_index] {
+            self.paused[child_index] = false;
+            let mut path = self.path.clone();
+            path.push(child_index);
+            self.activations.borrow_mut().activate(&path[..]);
         }
+    }
 
-        // Each child has expressed initial capabilities (their `shared_progress.internals`).
-        // We introduce these into the progress tracker to determine the scope's initial
-        // internal capabilities.
-        for child in self.children.iter_mut() {
-            child.extract_progress(&mut self.final_pointstamp, &mut self.temp_active);
+    /// Schedules a child only while its input frontier satisfies `predicate`.
+    ///
+    /// The predicate is applied to the sorted frontier of outer times across all inputs of the child.
+    /// While it does not hold, the child is skipped when activated, and its input frontier changes
+    /// accumulate as they would for a paused child; each change to its input frontier activates it
+    /// again, so that it is scheduled once the predicate holds. This avoids scheduling children
+    /// that can do no useful work until their inputs have advanced far enough. Setting a predicate
+    /// again replaces the previous one. The predicate is not consulted by `step_child`.
+    pub fn activate_when<F: Fn(&[TOuter])->bool+'static>(&mut self, child_index: usize, predicate: F) {
+        assert!(child_index > 0 && child_index < self.children.len(), "Invalid child {} to gate", child_index);
+        self.gates[child_index] = Some(Box::new(predicate));
+    }
+
+    /// Indicates whether the input frontier of a child satisfies its predicate, if any.
+    fn gate_open(&mut self, child_index: usize) -> bool {
+        match &self.gates[child_index] {
+            Some(predicate) => {
+                self.gate_frontier.clear();
+                for target in self.pointstamp_tracker.node_state(child_index).targets.iter() {
+                    self.gate_frontier.extend(target.implications.frontier().iter().map(|time| time.clone().to_outer()));
+                }
+                self.gate_sorted.clear();
+                self.gate_sorted.extend(self.gate_frontier.elements().iter().cloned());
+                self.gate_sorted.sort();
+                predicate(&self.gate_sorted[..])
+            },
+            None => true,
         }
+    }
 
-        self.propagate_pointstamps();  // Propagate expressed capabilities to output frontiers.
+    /// Sets a high watermark for the number of outstanding messages at an input of a child.
+    ///
+    /// Input is not paused by the subgraph itself, as it does not control the rate at which its
+    /// sources produce messages. Instead, drivers should consult `backpressured` and refrain from
+    /// introducing input while it reports true. Watermarks are initially unbounded, and setting a
+    /// watermark for the same input again replaces the previous watermark.
+    pub fn set_backpressure(&mut self, child_index: usize, input: usize, high_watermark: i64) {
+        let target = Target::new(child_index, input);
+        self.backpressure.retain(|(t, _)| t != &target);
+        self.backpressure.push((target, high_watermark));
+    }
 
-        // Return summaries and shared progress information.
-        (internal_summary, self.shared_progress.clone())
+    /// Indicates whether any input with a watermark has more outstanding messages than it allows.
+    pub fn backpressured(&self) -> bool {
+        self.backpressure.iter().any(|(target, high_watermark)| {
+            let outstanding =
+            self.pointstamp_tracker
+                .node_state(target.node)
+                .targets[target.port]
+                .pointstamps
+                .updates()
+                .map(|(_time, diff)| diff)
+                .sum::<i64>();
+            outstanding > *high_watermark
+        })
     }
 
-    fn set_external_summary(&mut self) {
-        self.propagate_pointstamps();  // ensure propagation of input frontiers.
-        self.children
-            .iter_mut()
-            .flat_map(|child| child.operator.as_mut())
-            .for_each(|op| op.set_external_summary());
+    /// Holds the capability to produce output at `time` on `output` until the returned guard is dropped.
+    ///
+    /// While the guard is live the subgraph reports a capability at `time` for `output` to its
+    /// parent, even if no child retains a corresponding capability. This allows the subgraph's
+    /// owner to keep the output frontier from passing `time` while work that may produce output
+    /// at `time` happens outside of the dataflow. Holds stack, and the capability is released
+    /// only once all holds at `time` are dropped.
+    ///
+    /// This method panics if the subgraph does not hold a capability for `output` at a time less
+    /// or equal to `time`, as the output frontier may already have passed `time`.
+    pub fn hold_output(&mut self, output: usize, time: TOuter) -> OutputHold<TOuter> {
+        if !self.output_capabilities[output].less_equal(&time) {
+            panic!("Attempted to hold output {} at {:?}, which is not `less_equal` its capabilities.", output, time);
+        }
+        let mut shared_progress = self.shared_progress.borrow_mut();
+        let quantum = &self.frontier_quantum;
+        let floor = &self.output_floors[output];
+        let window = &self.reorder_windows[output];
+        Some((time.clone(), 1))
+            .filter_through(&mut self.output_capabilities[output])
+            .map(|(time, diff)| (report_time(quantum, window, floor, time), diff))
+            .filter_through(&mut self.reported_capabilities[output])
+            .for_each(|(time, diff)| shared_progress.internals[output].update(time, diff));
+        drop(shared_progress);
+        self.update_probes();
+        OutputHold {
+            time,
+            holds: self.output_holds[output].clone(),
+            activator: Activator::new(&self.path[..], self.activations.clone()),
+        }
     }
-}
 
-struct PerOperatorState<T: Timestamp> {
+    /// Schedules only the children with work at the least time with work, and returns that time.
+    ///
+    /// The scheduling begins and ends as `schedule` does: it is counted, progress from the parent
+    /// and from peers is accepted, and produced progress is sent and the reported frontiers recorded.
+    /// The least time at which any child holds a capability or has outstanding messages is then
+    /// found, and the children with either at exactly that time are scheduled in order of their
+    /// index, regardless of their activations, before their progress is applied. Activated children
+    /// that are not scheduled remain activated for the next call to `schedule`, and those that are
+    /// scheduled do not. As the order of times does not depend on how the children are arranged,
+    /// repeatedly stepping the subgraph executes deterministically, though more slowly than
+    /// scheduling all active children. Among incomparable times the least in the total order of the
+    /// timestamp is stepped first. The result is `None` if no child has work.
+    ///
+    /// Children are opaque, so a child scheduled for one time may still act on others.
+    pub fn step_timestamp_ordered(&mut self) -> Option<TInner> {
 
-    name: String,       // name of the operator
-    index: usize,       // index of the operator within its parent scope
-    id: usize,          // worker-unique identifier
+        self.begin_tick();
 
-    local: bool,        // indicates whether the operator will exchange data or not
-    notify: bool,
-    inputs: usize,      // number of inputs to the operator
-    outputs: usize,     // number of outputs from the operator
+        let tracker = &self.pointstamp_tracker;
+        let has_work = |index: usize, time: &TInner| {
+            let state = tracker.node_state(index);
+            state.targets.iter().chain(state.sources.iter()).any(|port| port.pointstamps.count_for(time) > 0)
+        };
+        let time =
+        (1 .. self.children.len())
+            .flat_map(|index| {
+                let state = tracker.node_state(index);
+                state.targets.iter().chain(state.sources.iter())
+                    .flat_map(|port| port.pointstamps.updates())
+                    .map(move |(time, _)| (index, time))
+            })
+            .filter(|(index, time)| has_work(*index, time))
+            .map(|(_, time)| time)
+            .min()
+            .cloned();
 
-    operator: Option<Box<dyn Operate<T>>>,
+        if let Some(time) = &time {
+            let ready =
+            (1 .. self.children.len())
+                .filter(|index| has_work(*index, time))
+                .collect::<Vec<_>>();
+            let ready = ready.into_iter().filter(|index| !self.paused[*index] && self.gate_open(*index)).collect::<Vec<_>>();
 
-    edges: Vec<Vec<Target>>,    // edges from the outputs of the operator
+            // Retain the activations of children not scheduled now.
+            let mut pending = ::std::mem::take(&mut self.temp_active).into_vec();
+            pending.retain(|Reverse(index)| !ready.contains(index));
+            self.temp_active.extend(pending);
 
-    shared_progress: Rc<RefCell<SharedProgress<T>>>,
+            for index in ready {
+                self.activate_child(index);
+            }
+            self.send_progress();
+            self.progcaster.recv(&mut self.final_pointstamp);
+            self.propagate_pointstamps();
+        }
 
-    internal_summary: Vec<Vec<Antichain<T::Summary>>>,   // cached result from get_internal_summary.
+        self.end_tick();
 
-    logging: Option<Logger>,
-}
+        time
+    }
 
-impl<T: Timestamp> PerOperatorState<T> {
+    /// Drains the progress the subgraph has reported to its parent into an owned report.
+    ///
+    /// This is meant for driving a subgraph on its own, rather than through a parent scope, and
+    /// takes the statements the parent would otherwise drain from the subgraph's shared progress.
+    pub fn pull_progress(&mut self) -> ProgressReport<TOuter> {
+        self.shared_progress.borrow_mut().report()
+    }
 
-    fn empty(inputs: usize, outputs: usize) -> PerOperatorState<T> {
-        PerOperatorState {
-            name:       "External".to_owned(),
-            operator:   None,
-            index:      0,
-            id:         usize::max_value(),
-            local:      false,
-            notify:     true,
-            inputs,
-            outputs,
+    /// Schedules the subgraph until it completes, recording its output frontiers after each scheduling.
+    ///
+    /// The subgraph is scheduled at most `max_ticks` times, and the shared activations are advanced
+    /// before each scheduling, as a worker would between steps. The result contains, for each
+    /// scheduling, the frontier of capabilities reported for each output. This method is meant for
+    /// driving a subgraph on its own, for example to visualize how a computation converges, and
+    /// should not be used with subgraphs that are hosted in a worker.
+    pub fn run_recording(&mut self, max_ticks: usize) -> Vec<Vec<Vec<TOuter>>> {
+        let mut frontiers = Vec::new();
+        for _tick in 0 .. max_ticks {
+            self.activations.borrow_mut().advance();
+            let incomplete = self.schedule();
+            frontiers.push(self.reported_capabilities.iter().map(|caps| caps.frontier().to_vec()).collect());
+            if !incomplete { break; }
+        }
+        frontiers
+    }
 
-            edges: vec![Vec::new(); outputs],
+    /// Schedules the subgraph until it completes, collecting the messages produced at each output.
+    ///
+    /// The subgraph is scheduled at most `max_ticks` times, as by `run_recording`. The result contains,
+    /// for each output, the consolidated counts of messages produced at each time, in order of time.
+    /// As with `run_recording`, this method is meant for driving a subgraph on its own, and takes the
+    /// produced message counts the parent would otherwise drain.
+    pub fn run_and_collect_outputs(&mut self, max_ticks: usize) -> Vec<Vec<(TOuter, i64)>> {
+        let mut collected = vec![ChangeBatch::new(); self.outputs];
+        for _tick in 0 .. max_ticks {
+            self.activations.borrow_mut().advance();
+            let incomplete = self.schedule();
+            for (output, produced) in self.shared_progress.borrow_mut().produceds.iter_mut().enumerate() {
+                collected[output].extend(produced.drain());
+            }
+            if !incomplete { break; }
+        }
+        collected.into_iter().map(|batch| batch.into_inner()).collect()
+    }
 
-            logging: None,
+    /// Enables or disables recording the changes to children's input frontiers.
+    ///
+    /// When enabled, the changes made to the input frontiers of each child in a scheduling are
+    /// retained until the next scheduling, and may be read with `last_frontier_changes`.
+    pub fn record_frontier_changes(&mut self, record: bool) {
+        self.frontier_changes = if record { Some(vec![Vec::new(); self.children.len()]) } else { None };
+    }
 
-            shared_progress: Rc::new(RefCell::new(SharedProgress::new(inputs,outputs))),
-            internal_summary: Vec::new(),
-        }
+    /// The changes to the input frontiers of a child in the most recent scheduling.
+    ///
+    /// Each change lists the input, the time, and the change in its count. The result is empty
+    /// unless changes are recorded, as enabled by `record_frontier_changes`.
+    pub fn last_frontier_changes(&self, child_index: usize) -> Vec<(usize, TInner, i64)> {
+        self.frontier_changes
+            .as_ref()
+            .map(|changes| changes[child_index].clone())
+            .unwrap_or_default()
     }
 
-    pub fn new(
-        mut scope: Box<dyn Operate<T>>,
-        index: usize,
-        mut _path: Vec<usize>,
-        identifier: usize,
-        logging: Option<Logger>
-    ) -> PerOperatorState<T>
-    {
-        let local = scope.local();
-        let inputs = scope.inputs();
-        let outputs = scope.outputs();
-        let notify = scope.notify_me();
+    /// Enables or disables tracking the capabilities children acquire and release.
+    ///
+    /// When enabled, each change to the capabilities of a child is recorded in a ledger once it is
+    /// applied, and `capability_ledger` reports the net balances. Tracking from before the call to
+    /// `get_internal_summary` includes the children's initial capabilities. Disabling tracking
+    /// discards the ledger.
+    pub fn track_capabilities(&mut self, track: bool) {
+        self.capability_ledger = if track { Some(ChangeBatch::new()) } else { None };
+    }
 
-        let (internal_summary, shared_progress) = scope.get_internal_summary();
+    /// The net balances of capabilities acquired by children and not yet released.
+    ///
+    /// Each entry lists the output of a child, the time, and the number of capabilities acquired
+    /// since tracking was enabled, less the number released; entries with a balance of zero are
+    /// omitted. The balances cover all workers once their progress updates have been received. A
+    /// run in which every acquired capability is released produces an empty ledger. The result is
+    /// empty unless capabilities are tracked, as enabled by `track_capabilities`.
+    pub fn capability_ledger(&self) -> Vec<(Location, TInner, i64)> {
+        self.capability_ledger
+            .as_ref()
+            .map(|ledger| ledger.clone().into_inner().into_iter().map(|((location, time), diff)| (location, time, diff)).collect())
+            .unwrap_or_default()
+    }
 
-        assert_eq!(internal_summary.len(), inputs);
-        assert!(!internal_summary.iter().any(|x| x.len() != outputs));
+    /// Retains the reported output frontiers after each of the most recent `depth` schedulings.
+    ///
+    /// The history is initially not recorded, which is also the case for a depth of zero. Reducing
+    /// the depth discards the oldest recorded frontiers.
+    pub fn set_history_depth(&mut self, depth: usize) {
+        self.history_depth = depth;
+        while self.frontier_history.len() > depth {
+            self.frontier_history.pop_front();
+        }
+    }
 
-        PerOperatorState {
-            name:               scope.name().to_owned(),
-            operator:           Some(scope),
-            index,
-            id:                 identifier,
-            local,
-            notify,
-            inputs,
-            outputs,
-            edges:              vec![vec![]; outputs],
-
-            logging,
-
-            shared_progress,
-            internal_summary,
+    /// The frontier reported for `output` after the scheduling `ticks_ago` schedulings before the last.
+    ///
+    /// The frontier after the most recent scheduling is zero ticks ago. The result is `None` if the
+    /// scheduling is not retained in the history, as configured by `set_history_depth`.
+    pub fn frontier_at(&self, output: usize, ticks_ago: usize) -> Option<Vec<TOuter>> {
+        let len = self.frontier_history.len();
+        if ticks_ago < len {
+            Some(self.frontier_history[len - 1 - ticks_ago][output].clone())
+        }
+        else {
+            None
         }
     }
 
-    pub fn schedule(&mut self) -> bool {
+    /// Shrinks the buffers used to exchange and apply progress updates after a burst.
+    ///
+    /// The buffers are retained between schedulings to avoid reallocation, and grow to fit the
+    /// largest batch of updates they have seen. This method shrinks each buffer whose contents
+    /// fit within `baseline` updates back to a capacity of `baseline`.
+    pub fn trim_buffers(&mut self, baseline: usize) {
+        self.local_pointstamp.shrink_to(baseline);
+        self.final_pointstamp.shrink_to(baseline);
+        if self.temp_active.len() <= baseline && self.temp_active.capacity() > baseline {
+            let mut temp_active = BinaryHeap::with_capacity(baseline);
+            temp_active.append(&mut self.temp_active);
+            self.temp_active = temp_active;
+        }
+    }
 
-        if let Some(ref mut operator) = self.operator {
+    /// The number of messages at `time` outstanding at the inputs of children.
+    ///
+    /// Messages are counted only at exactly `time`, and only once they have been reported as
+    /// produced. Messages within a nested subgraph are counted at the input of the nested subgraph
+    /// while they are outstanding there; once the nested subgraph has consumed them it reports
+    /// their consequences as capabilities on its outputs, which are not counted.
+    pub fn in_flight_at(&self, time: &TInner) -> i64 {
+        (1 .. self.children.len())
+            .flat_map(|index| self.pointstamp_tracker.node_state(index).targets.iter())
+            .flat_map(|target| target.pointstamps.updates())
+            .filter(|(t, _)| t == time)
+            .map(|(_, diff)| diff)
+            .sum()
+    }
 
-            // Perhaps log information about the start of the schedule call.
-            if let Some(l) = self.logging.as_mut() {
-                // FIXME: There is no contract that the operator must consume frontier changes.
-                //        This report could be spurious.
-                // TODO:  Perhaps fold this in to `ScheduleEvent::start()` as a "reason"?
-                let frontiers = &mut self.shared_progress.borrow_mut().frontiers[..];
-                if frontiers.iter_mut().any(|buffer| !buffer.is_empty()) {
-                    l.log(crate::logging::PushProgressEvent { op_id: self.id })
-                }
+    /// The numbers of messages outstanding at input `input` of child `child_index`, by time.
+    ///
+    /// As with `in_flight_at`, messages are counted once they have been reported as produced, until
+    /// they are reported as consumed. The result is sorted by time, and omits times without messages,
+    /// so that a child may choose the next time to work on.
+    pub fn outstanding_by_time(&self, child_index: usize, input: usize) -> Vec<(TInner, i64)> {
+        let mut counts = ChangeBatch::new();
+        counts.extend(
+            self.pointstamp_tracker
+                .node_state(child_index)
+                .targets[input]
+                .pointstamps
+                .updates()
+                .cloned()
+        );
+        counts.into_inner()
+    }
 
-                l.log(crate::logging::ScheduleEvent::start(self.id));
-            }
+    /// The changes to children's input frontiers that advancing scope input `input` to `time` would cause.
+    ///
+    /// The advance replaces the frontier of `input` that the subgraph has accepted from its parent by
+    /// `time`, and propagates the consequences through a copy of the subgraph's progress tracker,
+    /// leaving the subgraph itself untouched. Children are opaque and are not scheduled, so their
+    /// reactions to the new frontiers are not simulated; as an input frontier alone does not change
+    /// the capabilities the subgraph reports, the changes to child frontiers are the full outcome.
+    /// Changes from the parent not yet accepted by scheduling the subgraph are not reflected.
+    pub fn simulate_input_advance(&self, input: usize, time: TOuter) -> Vec<(Target, TInner, i64)> {
+        assert!(input < self.inputs, "Input {} out of range for {} inputs", input, self.inputs);
+        let mut tracker = self.pointstamp_tracker.clone();
+        let source = Source::new(0, input);
+        let current = self.pointstamp_tracker.node_state(0).sources[input].pointstamps.updates().cloned().collect::<Vec<_>>();
+        for (time, diff) in current {
+            tracker.update_source(source, time, -diff);
+        }
+        tracker.update_source(source, TInner::to_inner(time), 1);
+        tracker.propagate_all();
 
-            let incomplete = operator.schedule();
+        let mut changes =
+        tracker
+            .pushed()
+            .drain()
+            .filter_map(|((location, time), diff)| match location.port {
+                Port::Target(port) => Some((Target::new(location.node, port), time, diff)),
+                Port::Source(_) => None,
+            })
+            .collect::<Vec<_>>();
+        changes.sort();
+        changes
+    }
 
-            // Perhaps log information about the stop of the schedule call.
-            if let Some(l) = self.logging.as_mut() {
-                l.log(crate::logging::ScheduleEvent::stop(self.id));
+    /// The least outer times of all work within the subgraph, including its input frontiers.
+    ///
+    /// This is the frontier of the input frontiers accepted from the parent, the capabilities held by
+    /// children, and the messages outstanding at their inputs, expressed as outer times. Nested
+    /// subgraphs contribute through the capabilities they hold on their outputs, which reflect the
+    /// work within them, so the frontier covers the whole of the nested computation. The result is
+    /// sorted, and is empty once the subgraph is quiescent and its inputs are closed.
+    pub fn global_frontier(&self) -> Vec<TOuter> {
+        let mut frontier = Antichain::new();
+        for index in 0 .. self.children.len() {
+            let state = self.pointstamp_tracker.node_state(index);
+            for port in state.targets.iter().chain(state.sources.iter()) {
+                frontier.extend(
+                    port.pointstamps
+                        .updates()
+                        .filter(|(time, _)| port.pointstamps.count_for(time) > 0)
+                        .map(|(time, _)| time.clone().to_outer())
+                );
             }
+        }
+        let mut frontier = frontier.elements().to_vec();
+        frontier.sort();
+        frontier
+    }
 
-            incomplete
+    /// A coarse measure of the work pending within the subgraph.
+    ///
+    /// This sums the absolute counts of outstanding messages at the inputs of children and of
+    /// capabilities held at the outputs of children. Nested subgraphs contribute through the
+    /// capabilities they hold on their outputs, which they retain as long as they have work.
+    /// A result of zero indicates that no child has outstanding messages or capabilities.
+    pub fn pending_work(&self) -> i64 {
+        (1 .. self.children.len())
+            .map(|index| {
+                let state = self.pointstamp_tracker.node_state(index);
+                state.targets.iter().chain(state.sources.iter())
+                    .map(|port| port.pointstamps.absolute_count())
+                    .sum::<i64>()
+            })
+            .sum()
+    }
+
+    /// Reports capabilities a child acquired and messages it produced without justification by its
+    /// internal summary.
+    ///
+    /// Each capability a child acquires after initialization must be justified by a capability it
+    /// already holds for the same output at a time less or equal, or by a consumed message or an
+    /// input frontier element that its declared internal summary advances to a time less or equal.
+    /// Each message it produces must be justified likewise, or by a capability acquired with it.
+    /// A child that acquires other capabilities or produces other messages has an internal summary
+    /// inconsistent with its behavior, and the subgraph's progress tracking may be incorrect.
+    ///
+    /// Violations are recorded as the child reports its progress, and only while auditing is
+    /// enabled by `audit_subscopes`. At most `MAX_VIOLATIONS` distinct violations are retained
+    /// for each child, and further violations are only counted.
+    pub fn audit_subscope(&self, child_index: usize) -> Result<(), String> {
+        let child = &self.children[child_index];
+        if child.violations.is_empty() {
+            Ok(())
+        }
+        else if child.violations_omitted > 0 {
+            Err(format!("{} (child {}): {}; and {} more", child.name, child_index, child.violations.join("; "), child.violations_omitted))
         }
         else {
+            Err(format!("{} (child {}): {}", child.name, child_index, child.violations.join("; ")))
+        }
+    }
 
-            // If the operator is closed and we are reporting progress at it, something has surely gone wrong.
-            if self.shared_progress.borrow_mut().frontiers.iter_mut().any(|x| !x.is_empty()) {
-                println!("Operator prematurely shut down: {}", self.name);
-                println!("  {:?}", self.notify);
-                println!("  {:?}", self.shared_progress.borrow_mut().frontiers);
-                panic!();
-            }
+    /// Enables or disables auditing the progress children report against their internal summaries.
+    ///
+    /// Auditing examines the progress of each child as it is collected, which has a cost for each
+    /// scheduling of the child, and is initially disabled. Violations are reported by `audit_subscope`.
+    pub fn audit_subscopes(&mut self, audit: bool) {
+        self.audit = audit;
+    }
 
-            // A closed operator shouldn't keep anything open.
-            false
+    /// Children with outputs that have consumed messages but never produced any.
+    ///
+    /// Such a child, perhaps a filter that discards everything, often indicates a logic error. As a
+    /// child may reasonably take some time to produce output, the result is empty until the subgraph
+    /// has been scheduled at least `ticks_observed` times. Messages are counted from construction.
+    pub fn dead_scopes(&self, ticks_observed: usize) -> Vec<usize> {
+        if self.ticks < ticks_observed {
+            return Vec::new();
         }
+        (1 .. self.children.len())
+            .filter(|index| {
+                let child = &self.children[*index];
+                child.outputs > 0 && child.consumed > 0 && child.produced == 0
+            })
+            .collect()
     }
 
-    fn shut_down(&mut self) {
-        if self.operator.is_some() {
-            if let Some(l) = self.logging.as_mut() {
-                l.log(crate::logging::ShutdownEvent{ id: self.id });
+    /// Indicates whether two subgraphs with the same structure have the same progress state.
+    ///
+    /// The subgraphs are compared by the counts of capabilities and outstanding messages at each
+    /// location, the frontiers these imply at each location, and the capabilities the subgraphs
+    /// report on their outputs. Two replicas of a subgraph driven identically should remain equal.
+    pub fn progress_equals(&self, other: &Subgraph<TOuter, TInner>) -> bool {
+        self.children.len() == other.children.len() &&
+        (0 .. self.children.len()).all(|index| {
+            let this = self.pointstamp_tracker.node_state(index);
+            let that = other.pointstamp_tracker.node_state(index);
+            this.targets.len() == that.targets.len() &&
+            this.sources.len() == that.sources.len() &&
+            this.targets.iter().zip(that.targets.iter())
+                .chain(this.sources.iter().zip(that.sources.iter()))
+                .all(|(this, that)| {
+                    consolidated(&this.pointstamps) == consolidated(&that.pointstamps) &&
+                    sorted_frontier(&this.implications) == sorted_frontier(&that.implications)
+                })
+        }) &&
+        self.output_capabilities.len() == other.output_capabilities.len() &&
+        self.output_capabilities.iter().zip(other.output_capabilities.iter())
+            .all(|(this, that)| sorted_frontier(this) == sorted_frontier(that))
+    }
+
+    /// Updates the progress state of the subgraph to match that of `other`.
+    ///
+    /// This is meant for failing over between replicas of a subgraph, and updates the counts of
+    /// capabilities and outstanding messages at each location to those of `other`. The resulting
+    /// frontier changes are presented to children and to the parent scope as usual. The children
+    /// themselves are not changed, and should be in the same state as those of `other`.
+    ///
+    /// The progress state is shared by all workers, and each would apply the corrections of every
+    /// other, so this method is only for subgraphs of single-worker computations. Pending local
+    /// progress updates are exchanged first, and the corrections then exchanged as any other.
+    ///
+    /// This method panics if `other` does not have the same children as `self`, or if the subgraph
+    /// is shared with other workers.
+    pub fn adopt_progress(&mut self, other: &Subgraph<TOuter, TInner>) {
+
+        assert_eq!(self.peers, 1, "Subgraph {:?} adopts progress with {} workers", self.name, self.peers);
+        assert_eq!(self.children.len(), other.children.len());
+
+        self.flush_progress();
+
+        for index in 1 .. self.children.len() {
+            let this = self.pointstamp_tracker.node_state(index);
+            let that = other.pointstamp_tracker.node_state(index);
+            assert_eq!(this.targets.len(), that.targets.len());
+            assert_eq!(this.sources.len(), that.sources.len());
+
+            let ports =
+            this.targets.iter().zip(that.targets.iter()).enumerate().map(|(port, x)| (Location::new_target(index, port), x))
+                .chain(this.sources.iter().zip(that.sources.iter()).enumerate().map(|(port, x)| (Location::new_source(index, port), x)));
+
+            for (location, (this, that)) in ports {
+                for (time, diff) in this.pointstamps.updates() {
+                    self.local_pointstamp.update((location, time.clone()), -diff);
+                }
+                for (time, diff) in that.pointstamps.updates() {
+                    self.local_pointstamp.update((location, time.clone()), *diff);
+                }
             }
-            self.operator = None;
-            self.name = format!("{}(tombstone)", self.name);
         }
+
+        self.flush_progress();
     }
 
-    /// Extracts shared progress information and converts to pointstamp changes.
-    fn extract_progress(&mut self, pointstamps: &mut ChangeBatch<(Location, T)>, temp_active: &mut BinaryHeap<Reverse<usize>>) {
+    /// Records the counts of outstanding messages and capabilities of each child.
+    ///
+    /// The checkpoint may be restored with `restore_progress` into this subgraph, or into a later
+    /// version of it with additional children.
+    pub fn checkpoint_progress(&self) -> ProgressCheckpoint<TInner> {
+        let scopes = (1 .. self.children.len())
+            .map(|index| {
+                let state = self.pointstamp_tracker.node_state(index);
+                (
+                    self.children[index].name.clone(),
+                    state.targets.iter().map(|target| consolidated(&target.pointstamps)).collect(),
+                    state.sources.iter().map(|source| consolidated(&source.pointstamps)).collect(),
+                )
+            })
+            .collect();
+        ProgressCheckpoint { scopes }
+    }
 
-        let shared_progress = &mut *self.shared_progress.borrow_mut();
+    /// Updates the progress state of the subgraph to that recorded by `checkpoint`.
+    ///
+    /// Checkpointed children are matched to children of the subgraph by name, with the k-th child
+    /// of a name in the checkpoint matched to the k-th child of that name in the subgraph. This
+    /// allows a checkpoint to be restored into a version of the subgraph to which children have
+    /// been added, as long as children of a repeated name are added after those of the same name
+    /// already present. Unmatched children of the subgraph have their counts cleared. As with
+    /// `adopt_progress`, the resulting frontier changes are presented to children and to the
+    /// parent scope, and the children themselves are not changed. Also as with `adopt_progress`,
+    /// the corrections are exchanged as any other progress update, and this method is only for
+    /// subgraphs of single-worker computations.
+    ///
+    /// An error is returned, and the subgraph is left unchanged, if a checkpointed child has no
+    /// match in the subgraph or its match has different numbers of inputs or outputs. This method
+    /// panics if the subgraph is shared with other workers.
+    pub fn restore_progress(&mut self, checkpoint: &ProgressCheckpoint<TInner>) -> Result<(), String> {
 
-        // Migrate consumeds, internals, produceds into progress statements.
-        for (input, consumed) in shared_progress.consumeds.iter_mut().enumerate() {
-            let target = Location::new_target(self.index, input);
-            for (time, delta) in consumed.drain() {
-                pointstamps.update((target, time), -delta);
+        assert_eq!(self.peers, 1, "Subgraph {:?} restores progress with {} workers", self.name, self.peers);
+
+        // The children of each name, in order, and the number of each matched so far.
+        let mut by_name = HashMap::new();
+        for index in 1 .. self.children.len() {
+            by_name.entry(self.children[index].name.as_str()).or_insert_with(Vec::new).push(index);
+        }
+        let mut matched = HashMap::new();
+
+        let mut restored = vec![None; self.children.len()];
+        for (name, targets, sources) in checkpoint.scopes.iter() {
+            let count = matched.entry(name.as_str()).or_insert(0);
+            let index = by_name.get(name.as_str()).and_then(|indices| indices.get(*count)).cloned()
+                .ok_or_else(|| format!("Checkpointed scope {:?} is missing from subgraph {:?}", name, self.name))?;
+            *count += 1;
+            let child = &self.children[index];
+            if child.inputs != targets.len() || child.outputs != sources.len() {
+                return Err(format!(
+                    "Checkpointed scope {:?} has shape ({}, {}), but child {} has shape ({}, {})",
+                    name, targets.len(), sources.len(), index, child.inputs, child.outputs,
+                ));
             }
+            restored[index] = Some((targets, sources));
         }
-        for (output, internal) in shared_progress.internals.iter_mut().enumerate() {
-            let source = Location::new_source(self.index, output);
-            for (time, delta) in internal.drain() {
-                pointstamps.update((source, time.clone()), delta);
+
+        self.flush_progress();
+
+        for (index, restored) in restored.iter().enumerate().skip(1) {
+            let state = self.pointstamp_tracker.node_state(index);
+            let ports =
+            state.targets.iter().enumerate().map(|(port, x)| (Location::new_target(index, port), x))
+                .chain(state.sources.iter().enumerate().map(|(port, x)| (Location::new_source(index, port), x)));
+            for (location, port) in ports {
+                for (time, diff) in port.pointstamps.updates() {
+                    self.local_pointstamp.update((location, time.clone()), -diff);
+                }
+            }
+            if let Some((targets, sources)) = restored {
+                let ports =
+                targets.iter().enumerate().map(|(port, x)| (Location::new_target(index, port), x))
+                    .chain(sources.iter().enumerate().map(|(port, x)| (Location::new_source(index, port), x)));
+                for (location, counts) in ports {
+                    for (time, diff) in counts.iter() {
+                        self.local_pointstamp.update((location, time.clone()), *diff);
+                    }
+                }
             }
         }
-        for (output, produced) in shared_progress.produceds.iter_mut().enumerate() {
-            for (time, delta) in produced.drain() {
-                for target in &self.edges[output] {
-                    pointstamps.update((Location::from(*target), time.clone()), delta);
-                    temp_active.push(Reverse(target.node));
+
+        self.flush_progress();
+        Ok(())
+    }
+
+    /// Reports children that advance timestamps but are not part of a cycle.
+    ///
+    /// A child whose internal summary strictly advances timestamps from an input to an output,
+    /// as a loop's feedback operator does, is expected to have a path from that output back to
+    /// that input. The indices of children for which no such path exists are returned, which
+    /// most often indicates that the back-edge of a loop was not connected.
+    pub fn check_loop_closure(&self) -> Vec<usize> {
+        (1 .. self.children.len())
+            .filter(|&index| {
+                let child = &self.children[index];
+                let advancing =
+                (0 .. child.inputs)
+                    .flat_map(|input| (0 .. child.outputs).map(move |output| (input, output)))
+                    .filter(|&(input, output)| {
+                        child.internal_summary[input][output]
+                            .elements()
+                            .iter()
+                            .any(|summary| summary != &Default::default())
+                    })
+                    .collect::<Vec<_>>();
+
+                !advancing.is_empty() && advancing.iter().all(|&(input, output)| {
+                    self.trace_path(Source::new(index, output), Target::new(index, input)).is_none()
+                })
+            })
+            .collect()
+    }
+
+    /// Labels the edge from `source` to `target` for presentation, for example by `to_dot`.
+    ///
+    /// Labels have no effect on progress tracking. Labelling an edge again replaces its label.
+    /// This method panics if there is no edge from `source` to `target`.
+    pub fn annotate_edge(&mut self, source: Source, target: Target, label: String) {
+        if !self.children[source.node].edges.get(source.port).map(|edges| edges.contains(&target)).unwrap_or(false) {
+            panic!("Attempted to annotate a missing edge from {:?} to {:?}", source, target);
+        }
+        self.edge_labels.insert((source, target), label);
+    }
+
+    /// Describes the children and edges of the subgraph in the DOT graph language.
+    ///
+    /// Each child is a node labelled by its name, with node zero representing the inputs and
+    /// outputs of the subgraph itself. Each edge is labelled by its output and input ports,
+    /// followed by any label supplied with `annotate_edge`.
+    pub fn to_dot(&self) -> String {
+        let mut dot = format!("digraph {:?} {{\n", self.name);
+        for (index, child) in self.children.iter().enumerate() {
+            dot.push_str(&format!("  {} [label={:?}];\n", index, child.name));
+        }
+        for (index, child) in self.children.iter().enumerate() {
+            for (port, targets) in child.edges.iter().enumerate() {
+                for target in targets.iter() {
+                    let mut label = format!("{} -> {}", port, target.port);
+                    if let Some(annotation) = self.edge_labels.get(&(Source::new(index, port), *target)) {
+                        label.push_str(": ");
+                        label.push_str(annotation);
+                    }
+                    dot.push_str(&format!("  {} -> {} [label={:?}];\n", index, target.node, label));
                 }
             }
         }
+        dot.push_str("}\n");
+        dot
     }
 
-    /// Test the validity of `self.shared_progress`.
+    /// The children whose input frontiers may be affected by the frontier of a subgraph input.
     ///
-    /// The validity of shared progress information depends on both the external frontiers and the
-    /// internal capabilities, as events can occur that cannot be explained locally otherwise.
-    fn validate_progress(&mut self, child_state: &reachability::PerOperator<T>) {
+    /// These are the children reachable from `input` along edges and through the internal
+    /// connectivity of children, and are those that changes to the frontier of `input` may
+    /// notify. The result is sorted by child index.
+    pub fn scopes_downstream_of_input(&self, input: usize) -> Vec<usize> {
+        let mut reached = vec![false; self.children.len()];
+        self.mark_downstream(self.children[0].edges[input].clone(), &mut reached);
+        (1 .. self.children.len()).filter(|index| reached[*index]).collect()
+    }
 
-        let shared_progress = &mut *self.shared_progress.borrow_mut();
+    /// Children not reachable from any subgraph input.
+    ///
+    /// Such a child can never receive messages, and often indicates a wiring mistake. Children
+    /// without inputs generate messages without receiving any, and if `from_sources` is set they
+    /// are treated as subgraph inputs are, so that neither they nor the children reachable from
+    /// them are reported. Children that have shut down are not reported. The result is sorted
+    /// by child index.
+    pub fn unreachable_scopes(&self, from_sources: bool) -> Vec<usize> {
+        let mut reached = vec![false; self.children.len()];
+        let mut todo = self.children[0].edges.concat();
+        if from_sources {
+            for (child, reached) in self.children.iter().zip(reached.iter_mut()).skip(1) {
+                if child.inputs == 0 {
+                    *reached = true;
+                    todo.extend(child.edges.concat());
+                }
+            }
+        }
+        self.mark_downstream(todo, &mut reached);
+        (1 .. self.children.len())
+            .filter(|index| !reached[*index] && self.children[*index].operator.is_some())
+            .collect()
+    }
 
-        // Increments to internal capabilities require a consumed input message, a
-        for (output, internal) in shared_progress.internals.iter_mut().enumerate() {
-            for (time, diff) in internal.iter() {
-                if *diff > 0 {
-                    let consumed = shared_progress.consumeds.iter_mut().any(|x| x.iter().any(|(t,d)| *d > 0 && t.less_equal(time)));
-                    let internal = child_state.sources[output].pointstamps.less_equal(time);
-                    let external = child_state.targets.iter().any(|x| x.implications.less_equal(time));
-                    if !consumed && !internal && !external {
-                        panic!("Progress error; internal {:?}", self.name);
+    /// Marks as reached the children of `todo` and those reachable from them.
+    ///
+    /// From each input reached, only the outputs it is connected to by the child's internal summary
+    /// are followed, so a child is not reached through an input that does not lead to its outputs.
+    fn mark_downstream(&self, mut todo: Vec<Target>, reached: &mut [bool]) {
+        let mut visited = self.children.iter().map(|child| vec![false; child.inputs]).collect::<Vec<_>>();
+        while let Some(target) = todo.pop() {
+            if target.node != 0 && !visited[target.node][target.port] {
+                visited[target.node][target.port] = true;
+                reached[target.node] = true;
+                let child = &self.children[target.node];
+                for output in 0 .. child.outputs {
+                    if !child.internal_summary[target.port][output].elements().is_empty() {
+                        todo.extend(child.edges[output].iter().cloned());
                     }
                 }
             }
         }
-        for (output, produced) in shared_progress.produceds.iter_mut().enumerate() {
-            for (time, diff) in produced.iter() {
-                if *diff > 0 {
-                    let consumed = shared_progress.consumeds.iter_mut().any(|x| x.iter().any(|(t,d)| *d > 0 && t.less_equal(time)));
-                    let internal = child_state.sources[output].pointstamps.less_equal(time);
-                    let external = child_state.targets.iter().any(|x| x.implications.less_equal(time));
-                    if !consumed && !internal && !external {
-                        panic!("Progress error; produced {:?}", self.name);
+    }
+
+    /// Reconstructs a shortest path from `source` to `target` within the subgraph.
+    ///
+    /// The path lists each location visited, starting at `source` and ending at `target`, and
+    /// alternates between following edges from sources to targets, and following the internal
+    /// connectivity of children from their inputs to their outputs. Paths do not pass through
+    /// the scope's own inputs and outputs. The result is `None` if `target` is unreachable
+    /// from `source`.
+    pub fn trace_path(&self, source: Source, target: Target) -> Option<Vec<Location>> {
+
+        let source = Location::from(source);
+        let target = Location::from(target);
+
+        // Breadth-first search, recording the location from which each location was reached.
+        let mut reached_from = HashMap::new();
+        let mut queue = VecDeque::new();
+        reached_from.insert(source, source);
+        queue.push_back(source);
+
+        while let Some(location) = queue.pop_front() {
+
+            if location == target {
+                let mut path = vec![target];
+                let mut current = target;
+                while current != source {
+                    current = reached_from[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let child = &self.children[location.node];
+            let next = match location.port {
+                // Scope outputs lead out of the subgraph, and paths do not continue from them.
+                Port::Target(_) if location.node == 0 => Vec::new(),
+                Port::Source(port) => {
+                    child.edges[port]
+                        .iter()
+                        .map(|target| Location::from(*target))
+                        .collect::<Vec<_>>()
+                },
+                Port::Target(port) => {
+                    (0 .. child.outputs)
+                        .filter(|output| !child.internal_summary[port][*output].elements().is_empty())
+                        .map(|output| Location::new_source(location.node, output))
+                        .collect::<Vec<_>>()
+                },
+            };
+
+            for location2 in next {
+                if let Entry::Vacant(entry) = reached_from.entry(location2) {
+                    entry.insert(location);
+                    queue.push_back(location2);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Finds a path from a scope input to a scope output with a maximal summary.
+    ///
+    /// The path lists each location visited, starting at a scope input and ending at a scope
+    /// output, and its summary composes the internal summaries of the children it passes through.
+    /// Among paths whose summaries are incomparable, the first found is retained. Paths visit each
+    /// location at most once, so that a loop contributes at most one iteration to the summary.
+    /// The search considers every such path, and is meant for diagnosing small graphs. The result
+    /// is `None` if no scope output is reachable from a scope input.
+    pub fn critical_path(&self) -> Option<(Vec<Location>, TInner::Summary)> {
+        let mut best = None;
+        let mut path = Vec::new();
+        for input in 0 .. self.inputs {
+            self.extend_critical_path(Location::new_source(0, input), Default::default(), &mut path, &mut best);
+        }
+        best
+    }
+
+    /// Extends `path` to `location` with `summary`, and explores each extension of the result.
+    fn extend_critical_path(
+        &self,
+        location: Location,
+        summary: TInner::Summary,
+        path: &mut Vec<Location>,
+        best: &mut Option<(Vec<Location>, TInner::Summary)>,
+    )
+    {
+        if path.contains(&location) { return; }
+        path.push(location);
+        let child = &self.children[location.node];
+        match location.port {
+            Port::Target(_) if location.node == 0 => {
+                if best.as_ref().map(|(_, best)| best.less_than(&summary)).unwrap_or(true) {
+                    *best = Some((path.clone(), summary));
+                }
+            },
+            Port::Source(port) => {
+                for target in child.edges[port].iter() {
+                    self.extend_critical_path(Location::from(*target), summary.clone(), path, best);
+                }
+            },
+            Port::Target(port) => {
+                for output in 0 .. child.outputs {
+                    for internal in child.internal_summary[port][output].elements().iter() {
+                        if let Some(summary) = summary.followed_by(internal) {
+                            self.extend_critical_path(Location::new_source(location.node, output), summary, path, best);
+                        }
                     }
                 }
+            },
+        }
+        path.pop();
+    }
+
+    /// Counts the distinct paths from `source` to `target` within the subgraph.
+    ///
+    /// Paths follow edges from sources to targets, and the internal connectivity of children
+    /// from their inputs to their outputs, as in `trace_path`. Each path visits a location at
+    /// most once, so that loops do not lead to unboundedly many paths. A count greater than one
+    /// indicates that messages from `source` may reach `target` along several routes, which for
+    /// a `source` and `target` not on a common loop may be an accidental duplication.
+    pub fn path_count(&self, source: Source, target: Target) -> usize {
+        let mut path = Vec::new();
+        self.count_paths(Location::from(source), Location::from(target), &mut path)
+    }
+
+    /// Counts the paths from `location` to `target` that avoid the locations of `path`.
+    fn count_paths(&self, location: Location, target: Location, path: &mut Vec<Location>) -> usize {
+        if location == target { return 1; }
+        if path.contains(&location) { return 0; }
+        path.push(location);
+        let child = &self.children[location.node];
+        let count = match location.port {
+            // Scope outputs lead out of the subgraph, and paths do not continue from them.
+            Port::Target(_) if location.node == 0 => 0,
+            Port::Source(port) => {
+                child.edges[port]
+                    .iter()
+                    .map(|next| self.count_paths(Location::from(*next), target, path))
+                    .sum()
+            },
+            Port::Target(port) => {
+                (0 .. child.outputs)
+                    .filter(|output| !child.internal_summary[port][*output].elements().is_empty())
+                    .map(|output| self.count_paths(Location::new_source(location.node, output), target, path))
+                    .sum()
+            },
+        };
+        path.pop();
+        count
+    }
+}
+
+
+impl<TOuter, TInner> Subgraph<TOuter, TInner>
+where
+    TOuter: Timestamp+TotalOrder,
+    TInner: Timestamp+Refines<TOuter>,
+{
+    /// Seals the inputs of the subgraph through `epoch`.
+    ///
+    /// Each input frontier accepted from the parent is advanced to at least `epoch`, both now and as
+    /// the parent later changes it, and the advanced frontiers are propagated to the children. This
+    /// declares that no input will present data at times before `epoch`, and the subgraph panics if
+    /// it receives messages at such times. Epochs may only advance, as frontiers advanced through an
+    /// epoch cannot be recovered.
+    ///
+    /// The outer timestamp must be totally ordered, so that each frontier element is either before
+    /// `epoch`, and advanced to it, or not.
+    pub fn seal_epoch(&mut self, epoch: TOuter) {
+        if let Some(sealed) = &self.input_epoch {
+            assert!(sealed.less_equal(&epoch), "Subgraph {:?} sealed at {:?} after sealing at {:?}", self.name, epoch, sealed);
+        }
+        let sealed = Some(epoch);
+        for input in 0 .. self.inputs {
+            let source = Source::new(0, input);
+            let current = self.pointstamp_tracker.node_state(0).sources[input].pointstamps.updates().cloned().collect::<Vec<_>>();
+            for (time, diff) in current {
+                self.pointstamp_tracker.update_source(source, time.clone(), -diff);
+                self.pointstamp_tracker.update_source(source, seal_time(&sealed, time), diff);
             }
         }
+        self.input_epoch = sealed;
+        self.propagate_pointstamps();
     }
 }
 
-// Explicitly shut down the operator to get logged information.
-impl<T: Timestamp> Drop for PerOperatorState<T> {
-    fn drop(&mut self) {
-        self.shut_down();
+
+impl<TOuter, TInner> Subgraph<TOuter, TInner>
+where
+    TOuter: Timestamp+TryInto<i128>+TryFrom<i128>,
+    TInner: Timestamp+Refines<TOuter>,
+{
+    /// Counts the live times within the subgraph, bucketed by their outer timestamps.
+    ///
+    /// Live times are those of capabilities held at the outputs of children and of outstanding
+    /// messages at their inputs. The range from the least to the greatest live outer timestamp
+    /// is divided into at most `buckets` ranges of equal width, each reported by its least
+    /// timestamp and the sum of the absolute counts of live times within it. Ranges that begin
+    /// after the greatest live timestamp are omitted, and the result is empty if there are no
+    /// live times.
+    ///
+    /// This method panics if `buckets` is zero.
+    pub fn time_histogram(&self, buckets: usize) -> Vec<(TOuter, i64)> {
+
+        assert!(buckets > 0, "Histogram requires at least one bucket.");
+
+        let mut counts = ChangeBatch::new();
+        for index in 1 .. self.children.len() {
+            let state = self.pointstamp_tracker.node_state(index);
+            for port in state.targets.iter().chain(state.sources.iter()) {
+                counts.extend(port.pointstamps.updates().map(|(time, diff)| (time.clone().to_outer(), *diff)));
+            }
+        }
+
+        let counts =
+        counts
+            .into_inner()
+            .into_iter()
+            .map(|(time, diff)| (widen(time), diff.abs()))
+            .collect::<Vec<(i128, i64)>>();
+
+        // Consolidated counts are sorted by time.
+        let (lower, upper) = match (counts.first(), counts.last()) {
+            (Some(first), Some(last)) => (first.0, last.0),
+            _ => return Vec::new(),
+        };
+
+        // Offsets from the least live time are exact as unsigned, as is the start of each bucket.
+        let offset = |time: i128| time.wrapping_sub(lower) as u128;
+        let buckets = buckets as u128;
+        let width = (offset(upper) / buckets).saturating_add(1);
+        let bucket_of = |time: i128| (offset(time) / width).min(buckets - 1);
+
+        let mut histogram = Vec::new();
+        for bucket in 0 .. buckets {
+            let start = match bucket.checked_mul(width) {
+                Some(start) if start <= offset(upper) => lower.wrapping_add(start as i128),
+                _ => break,
+            };
+            let count = counts.iter().filter(|x| bucket_of(x.0) == bucket).map(|x| x.1).sum();
+            histogram.extend(narrow(start).map(|start| (start, count)));
+        }
+        histogram
+    }
+
+    /// The greatest time through which `output` is complete, if any.
+    ///
+    /// For totally ordered outer timestamps, the parent is told that the subgraph will produce no
+    /// further output at times less than the least capability reported for `output`, after any
+    /// reordering window, quantum, and floor, and the result is the time before it. The result is
+    /// `None` if a capability is reported at the least timestamp, and is the greatest timestamp
+    /// representable as an `i128` once no capabilities are reported for `output`.
+    pub fn output_complete_through(&self, output: usize) -> Option<TOuter> {
+        match self.reported_capabilities[output].frontier().iter().min() {
+            // Times before the least timestamp saturate, and are not before it.
+            Some(time) => widen(time.clone()).checked_sub(1).and_then(narrow).filter(|through| through < time),
+            None => narrow(i128::MAX),
+        }
+    }
+
+    /// Reports output capabilities only at multiples of `quantum`.
+    ///
+    /// Each outer time is rounded down to a multiple of `quantum`, saturating at the least timestamp,
+    /// before it is reported to the parent as a capability on an output, so that the output frontiers
+    /// only advance when they cross a multiple of `quantum`. Negative times are rounded down as well,
+    /// away from zero. Rounded capabilities are no greater than the capabilities they stand for, and
+    /// the output frontiers still become empty once the subgraph completes. Capabilities reported
+    /// before the call are re-expressed at the new granularity.
+    ///
+    /// This method panics if `quantum` is not positive.
+    pub fn set_frontier_quantum(&mut self, quantum: TOuter) {
+
+        let quantum = widen(quantum);
+        assert!(quantum > 0, "Frontier quantum must be positive.");
+
+        let round = move |time: TOuter| {
+            let wide = widen(time.clone());
+            narrow(wide.checked_sub(wide.rem_euclid(quantum)).unwrap_or(i128::MIN)).unwrap_or(time)
+        };
+
+        self.change_reporting(|subgraph| subgraph.frontier_quantum = Some(Box::new(round)));
+    }
+
+    /// Reports capabilities on `output` trailing the capabilities held by `window`.
+    ///
+    /// Each outer time is moved back by `window`, saturating at the least timestamp, before it is
+    /// reported to the parent as a capability on `output`, ahead of any rounding and flooring. This
+    /// allows children to produce output out of order at times within `window` of their capabilities,
+    /// while the parent observes as complete only the times before the capabilities less `window`.
+    /// Capabilities reported before the call are re-expressed with the new window.
+    ///
+    /// This method panics if `window` is negative.
+    pub fn set_reorder_window(&mut self, output: usize, window: TOuter) {
+
+        let window = widen(window);
+        assert!(window >= 0, "Reorder window must not be negative.");
+
+        let trail = move |time: TOuter| narrow(widen(time.clone()).saturating_sub(window)).unwrap_or(time);
+
+        self.change_reporting(|subgraph| subgraph.reorder_windows[output] = Some(Box::new(trail)));
+    }
+
+    /// Describes the progress state of the subgraph in the Prometheus text exposition format.
+    ///
+    /// This reports, each labelled by the name of the subgraph, the outstanding messages at the
+    /// inputs of each child, additionally labelled by the child's index and name, the least outer
+    /// time of the frontier of each subgraph output as formatted by `Debug`, or `+Inf` if the
+    /// frontier is empty, the pending work as by `pending_work`, and the number of times the
+    /// subgraph has been scheduled.
+    pub fn metrics_text(&self) -> String {
+
+        fn escape(value: &str) -> String {
+            value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+        }
+
+        let subgraph = escape(&self.name);
+        let mut text = String::new();
+
+        text.push_str("# TYPE timely_subgraph_outstanding_messages gauge\n");
+        for index in 1 .. self.children.len() {
+            let outstanding = self.pointstamp_tracker.node_state(index).targets.iter()
+                .map(|target| target.pointstamps.absolute_count())
+                .sum::<i64>();
+            text.push_str(&format!(
+                "timely_subgraph_outstanding_messages{{subgraph=\"{}\",scope=\"{}\",index=\"{}\"}} {}\n",
+                subgraph, escape(&self.children[index].name), index, outstanding,
+            ));
+        }
+
+        text.push_str("# TYPE timely_subgraph_output_frontier_min gauge\n");
+        for (output, target) in self.pointstamp_tracker.node_state(0).targets.iter().enumerate() {
+            let minimum = target.implications.frontier().iter()
+                .map(|time| time.clone().to_outer())
+                .min()
+                .map(|time| format!("{:?}", time))
+                .unwrap_or_else(|| "+Inf".to_owned());
+            text.push_str(&format!(
+                "timely_subgraph_output_frontier_min{{subgraph=\"{}\",output=\"{}\"}} {}\n",
+                subgraph, output, minimum,
+            ));
+        }
+
+        text.push_str("# TYPE timely_subgraph_pending_work gauge\n");
+        text.push_str(&format!("timely_subgraph_pending_work{{subgraph=\"{}\"}} {}\n", subgraph, self.pending_work()));
+
+        text.push_str("# TYPE timely_subgraph_ticks_total counter\n");
+        text.push_str(&format!("timely_subgraph_ticks_total{{subgraph=\"{}\"}} {}\n", subgraph, self.ticks));
+
+        text
+    }
+
+    /// Reports capabilities that lag the input frontiers of the children holding them.
+    ///
+    /// A capability is reported as `(child, output, time)` if the outer timestamp of every element
+    /// of the child's input frontiers exceeds that of the capability by more than `age_threshold`.
+    /// Such capabilities can no longer be explained by the child's inputs, and may indicate that
+    /// the child has failed to release them. Children without inputs, or whose input frontiers are
+    /// empty, are not considered. A leaked capability within a nested subgraph is reported as a
+    /// capability held by the nested subgraph itself.
+    pub fn detect_leaks(&self, age_threshold: TOuter) -> Vec<(usize, usize, TOuter)> {
+
+        // Capabilities lag frontier elements, so ages are exact as unsigned, and positive.
+        let age_threshold = u128::try_from(widen(age_threshold)).unwrap_or(0);
+
+        let mut leaks = Vec::new();
+        for index in 1 .. self.children.len() {
+            let state = self.pointstamp_tracker.node_state(index);
+            let frontier =
+            state
+                .targets
+                .iter()
+                .flat_map(|target| target.implications.frontier().to_vec())
+                .map(|time| widen(time.to_outer()))
+                .collect::<Vec<_>>();
+
+            if !frontier.is_empty() {
+                for (output, source) in state.sources.iter().enumerate() {
+                    for time in source.pointstamps.frontier().iter() {
+                        let outer = time.clone().to_outer();
+                        let lag = widen(outer.clone());
+                        if frontier.iter().all(|f| *f > lag && f.wrapping_sub(lag) as u128 > age_threshold) {
+                            leaks.push((index, output, outer));
+                        }
+                    }
+                }
+            }
+        }
+        leaks
+    }
+}
+
+impl<TOuter, TInner> Operate<TOuter> for Subgraph<TOuter, TInner>
+where
+    TOuter: Timestamp,
+    TInner: Timestamp+Refines<TOuter>,
+{
+    fn local(&self) -> bool { false }
+    fn inputs(&self)  -> usize { self.inputs }
+    fn outputs(&self) -> usize { self.outputs }
+
+    // produces connectivity summaries from inputs to outputs, and reports initial internal
+    // capabilities on each of the outputs (projecting capabilities from contained scopes).
+    fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<TOuter::Summary>>>, Rc<RefCell<SharedProgress<TOuter>>>) {
+
+        // double-check that child 0 (the outside world) is correctly shaped.
+        assert_eq!(self.children[0].outputs, self.inputs());
+        assert_eq!(self.children[0].inputs, self.outputs());
+
+        let mut internal_summary = vec![vec![Antichain::new(); self.outputs()]; self.inputs()];
+        for input in 0 .. self.scope_summary.len() {
+            for output in 0 .. self.scope_summary[input].len() {
+                for path_summary in self.scope_summary[input][output].elements().iter() {
+                    internal_summary[input][output].insert(TInner::summarize(path_summary.clone()));
+                }
+            }
+        }
+
+        // Each child has expressed initial capabilities (their `shared_progress.internals`).
+        // We introduce these into the progress tracker to determine the scope's initial
+        // internal capabilities.
+        for child in self.children.iter_mut() {
+            child.extract_progress(&mut self.final_pointstamp, &mut self.temp_active);
+        }
+
+        self.propagate_pointstamps();  // Propagate expressed capabilities to output frontiers.
+
+        self.summarized = true;
+
+        // Return summaries and shared progress information.
+        (internal_summary, self.shared_progress.clone())
+    }
+
+    fn set_external_summary(&mut self) {
+        if !self.summarized {
+            panic!("Subgraph {:?} received external summary before `get_internal_summary`", self.name);
+        }
+        self.sealed = true;
+        self.propagate_pointstamps();  // ensure propagation of input frontiers.
+        self.children
+            .iter_mut()
+            .flat_map(|child| child.operator.as_mut())
+            .for_each(|op| op.set_external_summary());
+    }
+}
+
+/// The time reported to the parent for a capability at `time`, after trailing, rounding, and flooring.
+fn report_time<T: Timestamp>(quantum: &Option<Box<dyn Fn(T)->T>>, window: &Option<Box<dyn Fn(T)->T>>, floor: &Option<T>, time: T) -> T {
+    let time = match window { Some(trail) => trail(time), None => time };
+    let time = match quantum { Some(round) => round(time), None => time };
+    match floor {
+        Some(floor) if time.less_than(floor) => floor.clone(),
+        _ => time,
+    }
+}
+
+/// A function moving an outer time before it is reported to the parent.
+type Retime<T> = Box<dyn Fn(T)->T>;
+
+/// A predicate on the input frontier of a child, which must hold for the child to be scheduled.
+type Gate<T> = Box<dyn Fn(&[T])->bool>;
+
+/// The value of an integer timestamp as an `i128`, saturating for `u128` values beyond its range.
+fn widen<T: TryInto<i128>>(time: T) -> i128 {
+    time.try_into().unwrap_or(i128::MAX)
+}
+
+/// The integer timestamp nearest to `value`, saturating at the least and greatest values of `T`.
+///
+/// The result is `None` only if `T` represents neither `value` nor any integer bound.
+fn narrow<T: TryFrom<i128>>(value: i128) -> Option<T> {
+    // Each integer type is bounded by an all-ones magnitude, its negation less one, or zero.
+    let bounds = (0 .. 128).rev().map(move |bits| if value < 0 { i128::MIN >> (127 - bits) } else { i128::MAX >> (127 - bits) });
+    T::try_from(value).ok().or_else(|| bounds.chain(Some(0)).flat_map(|bound| T::try_from(bound).ok()).next())
+}
+
+/// The time at which an input frontier element at `time` is tracked, after sealing through `epoch`.
+///
+/// Epochs are only sealed for totally ordered outer timestamps, by `Subgraph::seal_epoch`.
+fn seal_time<TOuter: Timestamp, TInner: Timestamp+Refines<TOuter>>(epoch: &Option<TOuter>, time: TInner) -> TInner {
+    match epoch {
+        Some(epoch) if time.clone().to_outer().less_than(epoch) => TInner::to_inner(epoch.clone()),
+        _ => time,
+    }
+}
+
+/// The counts of outstanding messages and capabilities of the children of a subgraph.
+///
+/// Produced by `Subgraph::checkpoint_progress`, and restored by `Subgraph::restore_progress`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgressCheckpoint<T> {
+    /// For each child, in order, its name, the counts of outstanding messages at each of its
+    /// inputs, and the counts of capabilities at each of its outputs.
+    pub scopes: Vec<(String, PortCounts<T>, PortCounts<T>)>,
+}
+
+/// Counts of times at each port of one kind of a child, indexed by port.
+pub type PortCounts<T> = Vec<Vec<(T, i64)>>;
+
+/// A held capability for a subgraph output, released when dropped.
+///
+/// Produced by `Subgraph::hold_output`. Dropping the hold activates the subgraph, which then
+/// reports the release of the capability to its parent.
+pub struct OutputHold<T: Timestamp> {
+    time: T,
+    holds: Rc<RefCell<ChangeBatch<T>>>,
+    activator: Activator,
+}
+
+impl<T: Timestamp> OutputHold<T> {
+    /// The timestamp at which the output is held.
+    pub fn time(&self) -> &T { &self.time }
+}
+
+impl<T: Timestamp> Drop for OutputHold<T> {
+    fn drop(&mut self) {
+        self.holds.borrow_mut().update(self.time.clone(), -1);
+        self.activator.activate();
+    }
+}
+
+/// A read-only view of the combined frontier of several subgraph outputs.
+///
+/// Produced by `Subgraph::combined_probe`, and inspected as a `probe::Handle` is. Clones share the
+/// same frontier, which only the subgraph updates.
+pub struct OutputProbe<T: Timestamp> {
+    frontier: Rc<RefCell<MutableAntichain<T>>>,
+}
+
+impl<T: Timestamp> OutputProbe<T> {
+    /// returns true iff the frontier is strictly less than `time`.
+    #[inline] pub fn less_than(&self, time: &T) -> bool { self.frontier.borrow().less_than(time) }
+    /// returns true iff the frontier is less than or equal to `time`.
+    #[inline] pub fn less_equal(&self, time: &T) -> bool { self.frontier.borrow().less_equal(time) }
+    /// returns true iff the frontier is empty.
+    #[inline] pub fn done(&self) -> bool { self.frontier.borrow().is_empty() }
+
+    /// Invokes a method on the frontier, returning its result.
+    #[inline]
+    pub fn with_frontier<R, F: FnMut(AntichainRef<T>)->R>(&self, mut function: F) -> R {
+        function(self.frontier.borrow().frontier())
+    }
+}
+
+impl<T: Timestamp> Clone for OutputProbe<T> {
+    fn clone(&self) -> Self {
+        OutputProbe {
+            frontier: self.frontier.clone()
+        }
+    }
+}
+
+/// The accumulated counts of `antichain`, sorted by time.
+fn consolidated<T: Timestamp>(antichain: &MutableAntichain<T>) -> Vec<(T, i64)> {
+    let mut counts = ChangeBatch::new();
+    counts.extend(antichain.updates().cloned());
+    counts.into_inner()
+}
+
+/// The frontier of `antichain`, sorted.
+fn sorted_frontier<T: Timestamp>(antichain: &MutableAntichain<T>) -> Vec<T> {
+    let mut frontier = antichain.frontier().to_vec();
+    frontier.sort();
+    frontier
+}
+
+/// The number of distinct violations `audit_subscope` retains for each child.
+pub const MAX_VIOLATIONS: usize = 16;
+
+struct PerOperatorState<T: Timestamp> {
+
+    name: String,       // name of the operator
+    index: usize,       // index of the operator within its parent scope
+    id: usize,          // worker-unique identifier
+
+    local: bool,        // indicates whether the operator will exchange data or not
+    notify: bool,
+    inputs: usize,      // number of inputs to the operator
+    outputs: usize,     // number of outputs from the operator
+
+    operator: Option<Box<dyn Operate<T>>>,
+
+    edges: Vec<Vec<Target>>,    // edges from the outputs of the operator
+
+    shared_progress: Rc<RefCell<SharedProgress<T>>>,
+
+    internal_summary: Vec<Vec<Antichain<T::Summary>>>,   // cached result from get_internal_summary.
+    violations: Vec<String>,    // distinct descriptions of progress not justified by `internal_summary`.
+    violations_omitted: usize,  // number of violations beyond `MAX_VIOLATIONS`.
+    consumed: i64,              // total messages reported consumed.
+    produced: i64,              // total messages reported produced.
+
+    logging: Option<Logger>,
+}
+
+impl<T: Timestamp> PerOperatorState<T> {
+
+    fn empty(inputs: usize, outputs: usize) -> PerOperatorState<T> {
+        PerOperatorState {
+            name:       "External".to_owned(),
+            operator:   None,
+            index:      0,
+            id:         usize::max_value(),
+            local:      false,
+            notify:     true,
+            inputs,
+            outputs,
+
+            edges: vec![Vec::new(); outputs],
+
+            logging: None,
+
+            shared_progress: Rc::new(RefCell::new(SharedProgress::new(inputs,outputs))),
+            internal_summary: Vec::new(),
+            violations: Vec::new(),
+            violations_omitted: 0,
+            consumed: 0,
+            produced: 0,
+        }
+    }
+
+    pub fn new(
+        mut scope: Box<dyn Operate<T>>,
+        index: usize,
+        mut _path: Vec<usize>,
+        identifier: usize,
+        logging: Option<Logger>
+    ) -> PerOperatorState<T>
+    {
+        let local = scope.local();
+        let inputs = scope.inputs();
+        let outputs = scope.outputs();
+        let notify = scope.notify_me();
+
+        let (internal_summary, shared_progress) = scope.get_internal_summary();
+
+        assert_eq!(internal_summary.len(), inputs);
+        assert!(!internal_summary.iter().any(|x| x.len() != outputs));
+
+        PerOperatorState {
+            name:               scope.name().to_owned(),
+            operator:           Some(scope),
+            index,
+            id:                 identifier,
+            local,
+            notify,
+            inputs,
+            outputs,
+            edges:              vec![vec![]; outputs],
+
+            logging,
+
+            shared_progress,
+            internal_summary,
+            violations: Vec::new(),
+            violations_omitted: 0,
+            consumed: 0,
+            produced: 0,
+        }
+    }
+
+    pub fn schedule(&mut self) -> bool {
+
+        if let Some(ref mut operator) = self.operator {
+
+            // Perhaps log information about the start of the schedule call.
+            if let Some(l) = self.logging.as_mut() {
+                // FIXME: There is no contract that the operator must consume frontier changes.
+                //        This report could be spurious.
+                // TODO:  Perhaps fold this in to `ScheduleEvent::start()` as a "reason"?
+                let frontiers = &mut self.shared_progress.borrow_mut().frontiers[..];
+                if frontiers.iter_mut().any(|buffer| !buffer.is_empty()) {
+                    l.log(crate::logging::PushProgressEvent { op_id: self.id })
+                }
+
+                l.log(crate::logging::ScheduleEvent::start(self.id));
+            }
+
+            let incomplete = operator.schedule();
+
+            // Perhaps log information about the stop of the schedule call.
+            if let Some(l) = self.logging.as_mut() {
+                l.log(crate::logging::ScheduleEvent::stop(self.id));
+            }
+
+            incomplete
+        }
+        else {
+
+            // If the operator is closed and we are reporting progress at it, something has surely gone wrong.
+            if self.shared_progress.borrow_mut().frontiers.iter_mut().any(|x| !x.is_empty()) {
+                println!("Operator prematurely shut down: {}", self.name);
+                println!("  {:?}", self.notify);
+                println!("  {:?}", self.shared_progress.borrow_mut().frontiers);
+                panic!();
+            }
+
+            // A closed operator shouldn't keep anything open.
+            false
+        }
+    }
+
+    fn shut_down(&mut self) {
+        if self.operator.is_some() {
+            if let Some(l) = self.logging.as_mut() {
+                l.log(crate::logging::ShutdownEvent{ id: self.id });
+            }
+            self.operator = None;
+            self.name = format!("{}(tombstone)", self.name);
+        }
+    }
+
+    /// Extracts shared progress information and converts to pointstamp changes.
+    fn extract_progress(&mut self, pointstamps: &mut ChangeBatch<(Location, T)>, temp_active: &mut BinaryHeap<Reverse<usize>>) {
+
+        let shared_progress = &mut *self.shared_progress.borrow_mut();
+
+        // Migrate consumeds, internals, produceds into progress statements.
+        for (input, consumed) in shared_progress.consumeds.iter_mut().enumerate() {
+            let target = Location::new_target(self.index, input);
+            for (time, delta) in consumed.drain() {
+                self.consumed += delta;
+                pointstamps.update((target, time), -delta);
+            }
+        }
+        for (output, internal) in shared_progress.internals.iter_mut().enumerate() {
+            let source = Location::new_source(self.index, output);
+            for (time, delta) in internal.drain() {
+                pointstamps.update((source, time.clone()), delta);
+            }
+        }
+        for (output, produced) in shared_progress.produceds.iter_mut().enumerate() {
+            for (time, delta) in produced.drain() {
+                self.produced += delta;
+                for target in &self.edges[output] {
+                    pointstamps.update((Location::from(*target), time.clone()), delta);
+                    temp_active.push(Reverse(target.node));
+                }
+            }
+        }
+    }
+
+    /// Test the validity of `self.shared_progress`.
+    ///
+    /// The validity of shared progress information depends on both the external frontiers and the
+    /// internal capabilities, as events can occur that cannot be explained locally otherwise.
+    fn validate_progress(&mut self, child_state: &reachability::PerOperator<T>) {
+
+        let shared_progress = &mut *self.shared_progress.borrow_mut();
+
+        // Increments to internal capabilities require a consumed input message, a
+        for (output, internal) in shared_progress.internals.iter_mut().enumerate() {
+            for (time, diff) in internal.iter() {
+                if *diff > 0 {
+                    let consumed = shared_progress.consumeds.iter_mut().any(|x| x.iter().any(|(t,d)| *d > 0 && t.less_equal(time)));
+                    let internal = child_state.sources[output].pointstamps.less_equal(time);
+                    let external = child_state.targets.iter().any(|x| x.implications.less_equal(time));
+                    if !consumed && !internal && !external {
+                        panic!("Progress error; internal {:?}", self.name);
+                    }
+                }
+            }
+        }
+        for (output, produced) in shared_progress.produceds.iter_mut().enumerate() {
+            for (time, diff) in produced.iter() {
+                if *diff > 0 {
+                    let consumed = shared_progress.consumeds.iter_mut().any(|x| x.iter().any(|(t,d)| *d > 0 && t.less_equal(time)));
+                    let internal = child_state.sources[output].pointstamps.less_equal(time);
+                    let external = child_state.targets.iter().any(|x| x.implications.less_equal(time));
+                    if !consumed && !internal && !external {
+                        panic!("Progress error; produced {:?}", self.name);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records acquired capabilities and produced messages that `self.internal_summary` does not justify.
+    ///
+    /// A capability must be justified by a capability already held for the same output, or by a
+    /// consumed message or input frontier element advanced by the summary to the output. A produced
+    /// message may also be justified by a capability acquired along with it.
+    fn audit_progress(&mut self, child_state: &reachability::PerOperator<T>) {
+
+        let mut violations = Vec::new();
+
+        {   // Scoped, to allow borrow to drop.
+            let shared_progress = &mut *self.shared_progress.borrow_mut();
+
+            // Times from which the summary may justify progress: consumed messages and input frontiers.
+            let mut candidates = Vec::new();
+            for (input, consumed) in shared_progress.consumeds.iter_mut().enumerate() {
+                candidates.extend(consumed.iter().filter(|(_, d)| *d > 0).map(|(t, _)| (input, t.clone())));
+            }
+            for (input, target) in child_state.targets.iter().enumerate() {
+                candidates.extend(target.implications.frontier().iter().map(|t| (input, t.clone())));
+            }
+
+            let summary = &self.internal_summary;
+            let justified = |output: usize, time: &T| {
+                child_state.sources[output].pointstamps.less_equal(time) ||
+                summary_justifies(summary, output, time, candidates.iter().map(|(input, t)| (*input, t)))
+            };
+
+            for (output, internal) in shared_progress.internals.iter_mut().enumerate() {
+                for (time, diff) in internal.iter() {
+                    if *diff > 0 && !justified(output, time) {
+                        violations.push(format!("capability at {:?} on output {} is not justified by its internal summary", time, output));
+                    }
+                }
+            }
+
+            for (output, produced) in shared_progress.produceds.iter_mut().enumerate() {
+                for (time, diff) in produced.iter() {
+                    let acquired = shared_progress.internals[output].iter().any(|(t, d)| *d > 0 && t.less_equal(time));
+                    if *diff > 0 && !acquired && !justified(output, time) {
+                        violations.push(format!("message at {:?} on output {} is not justified by its internal summary", time, output));
+                    }
+                }
+            }
+        }
+
+        for violation in violations {
+            if !self.violations.contains(&violation) {
+                if self.violations.len() < MAX_VIOLATIONS {
+                    self.violations.push(violation);
+                }
+                else {
+                    self.violations_omitted += 1;
+                }
+            }
+        }
+    }
+}
+
+// Explicitly shut down the operator to get logged information.
+impl<T: Timestamp> Drop for PerOperatorState<T> {
+    fn drop(&mut self) {
+        self.shut_down();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::rc::Rc;
+    use std::cell::{Cell, RefCell};
+
+    use crate::communication::allocator::thread::Thread;
+    use crate::scheduling::{Schedule, Scheduler};
+    use crate::scheduling::activate::Activator;
+    use crate::progress::{Operate, Antichain, ChangeBatch, Location, Source, Target};
+    use crate::progress::operate::SharedProgress;
+    use crate::worker::Worker;
+
+    use super::{Subgraph, SubgraphBuilder};
+
+    /// The progress statements of a child, shared with the test.
+    type Shared = Rc<RefCell<SharedProgress<usize>>>;
+
+    /// A child operator whose progress statements are written directly by the test.
+    struct Puppet {
+        path: Vec<usize>,
+        scheduled: Rc<Cell<usize>>,
+        inputs: usize,
+        outputs: usize,
+        summary: Vec<Vec<Antichain<usize>>>,
+        shared: Rc<RefCell<SharedProgress<usize>>>,
+    }
+
+    impl Puppet {
+        /// Adds a puppet to `builder` whose inputs reach its outputs with `summary`.
+        fn add(
+            builder: &mut SubgraphBuilder<usize, usize>,
+            inputs: usize,
+            outputs: usize,
+            summary: usize,
+        ) -> (usize, Rc<RefCell<SharedProgress<usize>>>)
+        {
+            let (index, shared, _scheduled) = Puppet::add_counted(builder, inputs, outputs, summary);
+            (index, shared)
+        }
+
+        /// Adds a puppet as `add` does, whose inputs reach its outputs as listed in `summary`.
+        fn add_summarized(
+            builder: &mut SubgraphBuilder<usize, usize>,
+            inputs: usize,
+            outputs: usize,
+            summary: Vec<Vec<Antichain<usize>>>,
+        ) -> (usize, Shared)
+        {
+            let (index, shared, _scheduled) = Puppet::insert(builder, inputs, outputs, summary);
+            (index, shared)
+        }
+
+        /// Adds a puppet as `add` does, also returning the number of times it has been scheduled.
+        fn add_counted(
+            builder: &mut SubgraphBuilder<usize, usize>,
+            inputs: usize,
+            outputs: usize,
+            summary: usize,
+        ) -> (usize, Shared, Rc<Cell<usize>>)
+        {
+            Puppet::insert(builder, inputs, outputs, vec![vec![Antichain::from_elem(summary); outputs]; inputs])
+        }
+
+        /// Adds a puppet with the given internal summary, returning its index, progress, and schedulings.
+        fn insert(
+            builder: &mut SubgraphBuilder<usize, usize>,
+            inputs: usize,
+            outputs: usize,
+            summary: Vec<Vec<Antichain<usize>>>,
+        ) -> (usize, Shared, Rc<Cell<usize>>)
+        {
+            let index = builder.allocate_child_id();
+            let mut path = builder.path.clone();
+            path.push(index);
+            let shared = Rc::new(RefCell::new(SharedProgress::new(inputs, outputs)));
+            let scheduled = Rc::new(Cell::new(0));
+            let puppet = Puppet {
+                path,
+                scheduled: scheduled.clone(),
+                inputs,
+                outputs,
+                summary,
+                shared: shared.clone(),
+            };
+            builder.add_child(Box::new(puppet), index, index);
+            (index, shared, scheduled)
+        }
+    }
+
+    impl Schedule for Puppet {
+        fn name(&self) -> &str { "Puppet" }
+        fn path(&self) -> &[usize] { &self.path[..] }
+        fn schedule(&mut self) -> bool {
+            self.scheduled.set(self.scheduled.get() + 1);
+            false
+        }
+    }
+
+    impl Operate<usize> for Puppet {
+        fn inputs(&self) -> usize { self.inputs }
+        fn outputs(&self) -> usize { self.outputs }
+        fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<usize>>>, Rc<RefCell<SharedProgress<usize>>>) {
+            (self.summary.clone(), self.shared.clone())
+        }
+    }
+
+    /// A child that holds a capability on its one output, advancing it each time it is scheduled.
+    struct Counter {
+        path: Vec<usize>,
+        bound: usize,
+        time: Option<usize>,
+        activator: Activator,
+        shared: Rc<RefCell<SharedProgress<usize>>>,
+    }
+
+    impl Counter {
+        /// Adds a counter to `builder` whose capability starts at zero and is released at `bound`.
+        fn add(builder: &mut SubgraphBuilder<usize, usize>, worker: &mut Worker<Thread>, bound: usize) -> usize {
+            let index = builder.allocate_child_id();
+            let mut path = builder.path.clone();
+            path.push(index);
+            let shared = Rc::new(RefCell::new(SharedProgress::new(0, 1)));
+            shared.borrow_mut().internals[0].update(0, 1);
+            let activator = Activator::new(&path[..], worker.activations());
+            let counter = Counter { path, bound, time: Some(0), activator, shared };
+            builder.add_child(Box::new(counter), index, index);
+            index
+        }
+    }
+
+    impl Schedule for Counter {
+        fn name(&self) -> &str { "Counter" }
+        fn path(&self) -> &[usize] { &self.path[..] }
+        fn schedule(&mut self) -> bool {
+            if let Some(time) = self.time.take() {
+                let mut shared = self.shared.borrow_mut();
+                shared.internals[0].update(time, -1);
+                if time + 1 < self.bound {
+                    shared.internals[0].update(time + 1, 1);
+                    self.time = Some(time + 1);
+                    self.activator.activate();
+                }
+            }
+            self.time.is_some()
+        }
+    }
+
+    impl Operate<usize> for Counter {
+        fn inputs(&self) -> usize { 0 }
+        fn outputs(&self) -> usize { 1 }
+        fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<usize>>>, Rc<RefCell<SharedProgress<usize>>>) {
+            (Vec::new(), self.shared.clone())
+        }
+    }
+
+    /// A child that produces one message on its one output at each of a sequence of times, logging each.
+    struct Emitter {
+        path: Vec<usize>,
+        stride: usize,
+        bound: usize,
+        time: Option<usize>,
+        log: Rc<RefCell<Vec<usize>>>,
+        shared: Rc<RefCell<SharedProgress<usize>>>,
+    }
+
+    impl Emitter {
+        /// Adds an emitter to `builder` producing at times from `start` below `bound`, `stride` apart.
+        fn add(
+            builder: &mut SubgraphBuilder<usize, usize>,
+            start: usize,
+            stride: usize,
+            bound: usize,
+            log: Rc<RefCell<Vec<usize>>>,
+        ) -> usize
+        {
+            let index = builder.allocate_child_id();
+            let mut path = builder.path.clone();
+            path.push(index);
+            let shared = Rc::new(RefCell::new(SharedProgress::new(0, 1)));
+            shared.borrow_mut().internals[0].update(start, 1);
+            let emitter = Emitter { path, stride, bound, time: Some(start), log, shared };
+            builder.add_child(Box::new(emitter), index, index);
+            index
+        }
+    }
+
+    impl Schedule for Emitter {
+        fn name(&self) -> &str { "Emitter" }
+        fn path(&self) -> &[usize] { &self.path[..] }
+        fn schedule(&mut self) -> bool {
+            if let Some(time) = self.time.take() {
+                let mut shared = self.shared.borrow_mut();
+                shared.produceds[0].update(time, 1);
+                shared.internals[0].update(time, -1);
+                self.log.borrow_mut().push(time);
+                if time + self.stride < self.bound {
+                    shared.internals[0].update(time + self.stride, 1);
+                    self.time = Some(time + self.stride);
+                }
+            }
+            self.time.is_some()
+        }
+    }
+
+    impl Operate<usize> for Emitter {
+        fn inputs(&self) -> usize { 0 }
+        fn outputs(&self) -> usize { 1 }
+        fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<usize>>>, Rc<RefCell<SharedProgress<usize>>>) {
+            (Vec::new(), self.shared.clone())
+        }
+    }
+
+    /// Builds and initializes a subgraph using `logic` to add children and edges.
+    fn build<F>(worker: &mut Worker<Thread>, logic: F) -> Subgraph<usize, usize>
+    where
+        F: FnOnce(&mut SubgraphBuilder<usize, usize>),
+    {
+        let mut builder = SubgraphBuilder::new_from(0, Vec::new(), None, "Test");
+        logic(&mut builder);
+        let mut subgraph = builder.build(worker);
+        subgraph.get_internal_summary();
+        subgraph.set_external_summary();
+        subgraph
+    }
+
+    /// Activates the listed children and schedules the subgraph until their progress is applied.
+    fn step(worker: &mut Worker<Thread>, subgraph: &mut Subgraph<usize, usize>, children: &[usize]) {
+        {   // Scoped, to allow borrow to drop.
+            let activations = worker.activations();
+            let mut activations = activations.borrow_mut();
+            for child in children.iter() {
+                let mut path = subgraph.path.clone();
+                path.push(*child);
+                activations.activate(&path[..]);
+            }
+            activations.advance();
+        }
+        // The first scheduling collects progress, the second receives and applies it.
+        subgraph.schedule();
+        worker.activations().borrow_mut().advance();
+        subgraph.schedule();
+    }
+
+    #[test]
+    fn compacted_summaries_propagate() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::new_from(0, Vec::new(), None, "Test");
+        builder.compact_summaries(true);
+        let (index0, shared0) = Puppet::add(&mut builder, 0, 1, 0);
+        let (index1, shared1) = Puppet::add(&mut builder, 1, 0, 0);
+        builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+        shared0.borrow_mut().internals[0].update(0, 1);
+        let mut subgraph = builder.build(&mut worker);
+        subgraph.get_internal_summary();
+        subgraph.set_external_summary();
+
+        assert_eq!(shared1.borrow_mut().frontiers[0].drain().collect::<Vec<_>>(), vec![(0, 1)]);
+
+        shared0.borrow_mut().internals[0].update(0, -1);
+        shared0.borrow_mut().internals[0].update(4, 1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        let mut changes = shared1.borrow_mut().frontiers[0].drain().collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(changes, vec![(0, -1), (4, 1)]);
+    }
+
+    #[test]
+    fn pending_work_drains() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut source = None;
+        let mut target = None;
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            source = Some((index0, shared0));
+            target = Some((index1, shared1));
+        });
+        let (index0, shared0) = source.unwrap();
+        let (index1, shared1) = target.unwrap();
+
+        assert_eq!(subgraph.pending_work(), 1);
+
+        // Produce three messages and release the capability.
+        shared0.borrow_mut().produceds[0].update(0, 3);
+        shared0.borrow_mut().internals[0].update(0, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+
+        let mut work = vec![subgraph.pending_work()];
+        for _ in 0 .. 3 {
+            shared1.borrow_mut().consumeds[0].update(0, 1);
+            step(&mut worker, &mut subgraph, &[index1]);
+            work.push(subgraph.pending_work());
+        }
+
+        assert_eq!(work, vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn step_child_handoff() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+            children.push((index1, shared1));
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        let (index0, shared0) = children.pop().unwrap();
+
+        let mut initial = shared1.borrow_mut().frontiers[0].drain().collect::<Vec<_>>();
+        initial.sort();
+        assert_eq!(initial, vec![(0, 1)]);
+
+        // Send two messages at time five, and release the capability.
+        shared0.borrow_mut().produceds[0].update(5, 2);
+        shared0.borrow_mut().internals[0].update(0, -1);
+        subgraph.step_child(index0);
+
+        let mut changes = shared1.borrow_mut().frontiers[0].drain().collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(changes, vec![(0, -1), (5, 1)]);
+
+        // Consume the messages.
+        shared1.borrow_mut().consumeds[0].update(5, 2);
+        subgraph.step_child(index1);
+
+        let mut changes = shared1.borrow_mut().frontiers[0].drain().collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(changes, vec![(5, -1)]);
+        assert_eq!(subgraph.pending_work(), 0);
+    }
+
+    #[test]
+    fn deterministic_trace() {
+
+        // Runs a chain of three children, recording all reported progress.
+        fn run() -> Vec<Vec<(usize, i64)>> {
+
+            let mut worker = Worker::new(Thread::new());
+
+            let mut children = Vec::new();
+            let mut subgraph = build(&mut worker, |builder| {
+                let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+                let (index1, shared1) = Puppet::add(builder, 1, 1, 1);
+                let (index2, shared2) = Puppet::add(builder, 1, 1, 0);
+                let output = builder.new_output();
+                builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+                builder.connect(Source::new(index1, 0), Target::new(index2, 0));
+                builder.connect(Source::new(index2, 0), Target::new(0, output.port));
+                shared0.borrow_mut().internals[0].update(0, 1);
+                children.push((index0, shared0));
+                children.push((index1, shared1));
+                children.push((index2, shared2));
+            });
+
+            let mut trace = Vec::new();
+            for round in 0 .. 5 {
+                // Each child consumes what it was sent in the previous round, and forwards it along.
+                for (index, shared) in children.iter() {
+                    let mut shared = shared.borrow_mut();
+                    if *index == children[0].0 {
+                        shared.produceds[0].update(round, 1);
+                        shared.internals[0].update(round, -1);
+                        shared.internals[0].update(round + 1, 1);
+                    }
+                    else if *index == children[1].0 {
+                        shared.consumeds[0].update(round, 1);
+                        shared.produceds[0].update(round + 1, 1);
+                    }
+                    else if round > 0 {
+                        shared.consumeds[0].update(round, 1);
+                        shared.produceds[0].update(round, 1);
+                    }
+                }
+                let indices = children.iter().map(|x| x.0).collect::<Vec<_>>();
+                step(&mut worker, &mut subgraph, &indices[..]);
+                for (_index, shared) in children.iter() {
+                    trace.push(shared.borrow_mut().frontiers.iter_mut().flat_map(|x| x.drain()).collect());
+                }
+                trace.push(subgraph.shared_progress.borrow_mut().internals[0].drain().collect());
+                trace.push(subgraph.shared_progress.borrow_mut().produceds[0].drain().collect());
+            }
+            trace
+        }
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn timestamp_ordered_independent_of_layout() {
+
+        // The messages produced at the output after each step.
+        type Outputs = Vec<(usize, Vec<(usize, i64)>)>;
+
+        // Runs emitters of even and odd times, in either order, recording the time of each step and
+        // the messages produced at the output.
+        fn run(evens_first: bool) -> (Vec<usize>, Outputs) {
+
+            let mut worker = Worker::new(Thread::new());
+
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let mut subgraph = build(&mut worker, |builder| {
+                let output = builder.new_output();
+                let starts = if evens_first { [0, 1] } else { [1, 0] };
+                for start in starts.iter() {
+                    let index = Emitter::add(builder, *start, 2, 6, log.clone());
+                    builder.connect(Source::new(index, 0), Target::new(0, output.port));
+                }
+            });
+
+            let mut trace = Vec::new();
+            while let Some(time) = subgraph.step_timestamp_ordered() {
+                trace.push((time, subgraph.shared_progress.borrow_mut().produceds[0].drain().collect()));
+            }
+            let log = log.borrow().clone();
+            (log, trace)
+        }
+
+        let (log, trace) = run(true);
+        assert_eq!(log, vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(trace.len(), 6);
+        assert_eq!(run(false), (log, trace));
+    }
+
+    #[test]
+    fn timestamp_ordered_records_schedulings() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut subgraph = build(&mut worker, |builder| {
+            let output = builder.new_output();
+            let index = Emitter::add(builder, 0, 1, 3, log.clone());
+            builder.connect(Source::new(index, 0), Target::new(0, output.port));
+        });
+        subgraph.set_history_depth(4);
+
+        // Each step is counted and its reported frontier recorded, as for `schedule`.
+        assert_eq!(subgraph.step_timestamp_ordered(), Some(0));
+        assert_eq!(subgraph.ticks, 1);
+        assert_eq!(subgraph.frontier_at(0, 0), Some(vec![1]));
+        assert_eq!(subgraph.step_timestamp_ordered(), Some(1));
+        assert_eq!(subgraph.step_timestamp_ordered(), Some(2));
+        assert_eq!(subgraph.step_timestamp_ordered(), None);
+        assert_eq!(subgraph.ticks, 4);
+        assert_eq!(subgraph.frontier_at(0, 1), Some(Vec::new()));
+        assert_eq!(subgraph.frontier_at(0, 2), Some(vec![2]));
+        assert_eq!(*log.borrow(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn hold_output_pins_frontier() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let output = builder.new_output();
+            builder.connect(Source::new(index0, 0), Target::new(0, output.port));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+        });
+        let (index0, shared0) = children.pop().unwrap();
+
+        let mut initial = subgraph.shared_progress.borrow_mut().internals[0].drain().collect::<Vec<_>>();
+        initial.sort();
+        assert_eq!(initial, vec![(0, 1)]);
+
+        // Hold the output at time three, twice, and release the child's capability.
+        let hold1 = subgraph.hold_output(0, 3);
+        let hold2 = subgraph.hold_output(0, 3);
+        shared0.borrow_mut().internals[0].update(0, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+
+        let mut changes = subgraph.shared_progress.borrow_mut().internals[0].drain().collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(changes, vec![(0, -1), (3, 1)]);
+
+        // Releasing one hold leaves the output frontier at three.
+        drop(hold1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert!(subgraph.shared_progress.borrow_mut().internals[0].is_empty());
+
+        // Releasing the last hold allows the output frontier to advance.
+        drop(hold2);
+        let incomplete = subgraph.schedule();
+        assert!(!incomplete);
+
+        let changes = subgraph.shared_progress.borrow_mut().internals[0].drain().collect::<Vec<_>>();
+        assert_eq!(changes, vec![(3, -1)]);
+    }
+
+    #[test]
+    fn trace_path_chain() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let (index0, _) = Puppet::add(builder, 0, 1, 0);
+            let (index1, _) = Puppet::add(builder, 1, 1, 0);
+            let (index2, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            builder.connect(Source::new(index1, 0), Target::new(index2, 0));
+            indices.extend(vec![index0, index1, index2]);
+        });
+
+        let path = subgraph.trace_path(Source::new(indices[0], 0), Target::new(indices[2], 0));
+        assert_eq!(path, Some(vec![
+            Location::new_source(indices[0], 0),
+            Location::new_target(indices[1], 0),
+            Location::new_source(indices[1], 0),
+            Location::new_target(indices[2], 0),
+        ]));
+
+        // Edges are directed, and nothing leads back to the first child.
+        assert_eq!(subgraph.trace_path(Source::new(indices[1], 0), Target::new(indices[1], 0)), None);
+    }
+
+    #[test]
+    fn trace_path_past_output() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        // The first child leads both to a scope output and, through the second child, to the third.
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let output = builder.new_output();
+            let (index0, _) = Puppet::add(builder, 1, 1, 0);
+            let (index1, _) = Puppet::add(builder, 1, 1, 0);
+            let (index2, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index0, 0));
+            builder.connect(Source::new(index0, 0), Target::new(0, output.port));
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            builder.connect(Source::new(index1, 0), Target::new(index2, 0));
+            indices.extend(vec![index0, index1, index2]);
+        });
+
+        // The search reaches the scope output before the third child, and does not continue from it.
+        let path = subgraph.trace_path(Source::new(0, 0), Target::new(indices[2], 0)).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(subgraph.trace_path(Source::new(indices[1], 0), Target::new(indices[0], 0)), None);
+        assert_eq!(
+            subgraph.trace_path(Source::new(0, 0), Target::new(0, 0)).map(|path| path.len()),
+            Some(4),
+        );
+    }
+
+    #[test]
+    fn path_count_diamond() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let (index0, _) = Puppet::add(builder, 0, 1, 0);
+            let (index1, _) = Puppet::add(builder, 1, 1, 0);
+            let (index2, _) = Puppet::add(builder, 1, 1, 0);
+            let (index3, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            builder.connect(Source::new(index0, 0), Target::new(index2, 0));
+            builder.connect(Source::new(index1, 0), Target::new(index3, 0));
+            builder.connect(Source::new(index2, 0), Target::new(index3, 0));
+            indices.extend(vec![index0, index1, index2, index3]);
+        });
+
+        assert_eq!(subgraph.path_count(Source::new(indices[0], 0), Target::new(indices[3], 0)), 2);
+        assert_eq!(subgraph.path_count(Source::new(indices[1], 0), Target::new(indices[3], 0)), 1);
+        assert_eq!(subgraph.path_count(Source::new(indices[1], 0), Target::new(indices[2], 0)), 0);
+    }
+
+    #[test]
+    fn path_count_past_output() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        // The first child leads both to a scope output and to the second child.
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let output = builder.new_output();
+            let (index0, _) = Puppet::add(builder, 1, 1, 0);
+            let (index1, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index0, 0));
+            builder.connect(Source::new(index0, 0), Target::new(0, output.port));
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            indices.extend(vec![index0, index1]);
+        });
+
+        assert_eq!(subgraph.path_count(Source::new(0, 0), Target::new(indices[1], 0)), 1);
+        assert_eq!(subgraph.path_count(Source::new(0, 0), Target::new(0, 0)), 1);
+    }
+
+    #[test]
+    fn critical_path_chain() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let output = builder.new_output();
+            let (index0, _) = Puppet::add(builder, 1, 1, 2);
+            let (index1, _) = Puppet::add(builder, 1, 1, 3);
+            let (index2, _) = Puppet::add(builder, 1, 1, 4);
+            builder.connect(Source::new(0, input.port), Target::new(index0, 0));
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            builder.connect(Source::new(index1, 0), Target::new(index2, 0));
+            builder.connect(Source::new(index2, 0), Target::new(0, output.port));
+            // A shortcut around the middle child.
+            builder.connect(Source::new(index0, 0), Target::new(index2, 0));
+            indices.extend(vec![index0, index1, index2]);
+        });
+
+        let (path, summary) = subgraph.critical_path().unwrap();
+        assert_eq!(summary, 9);
+        assert_eq!(path, vec![
+            Location::new_source(0, 0),
+            Location::new_target(indices[0], 0),
+            Location::new_source(indices[0], 0),
+            Location::new_target(indices[1], 0),
+            Location::new_source(indices[1], 0),
+            Location::new_target(indices[2], 0),
+            Location::new_source(indices[2], 0),
+            Location::new_target(0, 0),
+        ]);
+    }
+
+    #[test]
+    fn multiplexed_inputs_independent() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut streams = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            streams = builder.new_input_multiplexed(2);
+            for (target, _) in streams.iter() {
+                // Each child holds a capability, so that it is not shut down.
+                let (index, shared) = Puppet::add(builder, 1, 1, 0);
+                builder.connect(Source::new(0, target.port), Target::new(index, 0));
+                shared.borrow_mut().internals[0].update(0, 1);
+                children.push(shared);
+            }
+        });
+
+        let frontier = |shared: &Rc<RefCell<SharedProgress<usize>>>| {
+            let mut changes = shared.borrow_mut().frontiers[0].drain().collect::<Vec<_>>();
+            changes.sort();
+            changes
+        };
+
+        for (target, _) in streams.iter() {
+            subgraph.shared_progress.borrow_mut().frontiers[target.port].update(0, 1);
+        }
+        step(&mut worker, &mut subgraph, &[]);
+        assert_eq!(frontier(&children[0]), vec![(0, 1)]);
+        assert_eq!(frontier(&children[1]), vec![(0, 1)]);
+
+        // Records arrive on both streams, and then the first stream completes.
+        streams[0].1.borrow_mut().update(0, 3);
+        streams[1].1.borrow_mut().update(0, 2);
+        subgraph.shared_progress.borrow_mut().frontiers[streams[0].0.port].update(0, -1);
+        step(&mut worker, &mut subgraph, &[]);
+        let consumed = subgraph.pull_progress().consumeds;
+        assert_eq!(consumed, vec![vec![(0, 3)], vec![(0, 2)]]);
+
+        // Once its records are consumed, the first stream's frontier advances, the second's does not.
+        children[0].borrow_mut().consumeds[0].update(0, 3);
+        step(&mut worker, &mut subgraph, &[1]);
+        assert_eq!(frontier(&children[0]), vec![(0, -1)]);
+        assert_eq!(frontier(&children[1]), vec![]);
+        assert_eq!(subgraph.in_flight_at(&0), 2);
+    }
+
+    #[test]
+    fn time_histogram_buckets() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+        });
+        let (index0, shared0) = children.pop().unwrap();
+
+        assert_eq!(subgraph.time_histogram(3), vec![(0, 1)]);
+
+        // Capabilities at one and nine, and messages at seven.
+        shared0.borrow_mut().internals[0].update(1, 2);
+        shared0.borrow_mut().internals[0].update(9, 3);
+        shared0.borrow_mut().produceds[0].update(7, 2);
+        step(&mut worker, &mut subgraph, &[index0]);
+
+        assert_eq!(subgraph.time_histogram(1), vec![(0, 8)]);
+        assert_eq!(subgraph.time_histogram(2), vec![(0, 3), (5, 5)]);
+        assert_eq!(subgraph.time_histogram(4), vec![(0, 3), (3, 0), (6, 2), (9, 3)]);
+        assert_eq!(subgraph.time_histogram(20), vec![(0, 1), (1, 2), (2, 0), (3, 0), (4, 0), (5, 0), (6, 0), (7, 2), (8, 0), (9, 3)]);
+    }
+
+    #[test]
+    fn integer_times_saturate() {
+        use super::{narrow, widen};
+        assert_eq!(widen(-5i32), -5);
+        assert_eq!(widen(u128::MAX), i128::MAX);
+        assert_eq!(narrow::<i8>(-1000), Some(i8::MIN));
+        assert_eq!(narrow::<i8>(1000), Some(i8::MAX));
+        assert_eq!(narrow::<u64>(-1), Some(0));
+        assert_eq!(narrow::<u64>(i128::MAX), Some(u64::MAX));
+        assert_eq!(narrow::<i128>(i128::MIN), Some(i128::MIN));
+        assert_eq!(narrow::<usize>(7), Some(7));
+    }
+
+    #[test]
+    fn audit_subscope_violations() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 1, 1);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index1, shared1));
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        subgraph.audit_subscopes(true);
+
+        // The input frontier at zero justifies capabilities from one onward.
+        shared1.borrow_mut().internals[0].update(1, 1);
+        step(&mut worker, &mut subgraph, &[index1]);
+        assert_eq!(subgraph.audit_subscope(index1), Ok(()));
+
+        // Nothing justifies a capability at zero.
+        shared1.borrow_mut().internals[0].update(0, 1);
+        step(&mut worker, &mut subgraph, &[index1]);
+        let report = subgraph.audit_subscope(index1).unwrap_err();
+        assert!(report.contains("capability at 0 on output 0"));
+    }
+
+    #[test]
+    fn audit_subscope_messages() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 1, 2);
+            let (index2, shared2) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            builder.connect(Source::new(index1, 0), Target::new(index2, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index1, shared1));
+            children.push((index2, shared2));
+        });
+        let (_index2, _shared2) = children.pop().unwrap();
+        let (index1, shared1) = children.pop().unwrap();
+        subgraph.audit_subscopes(true);
+
+        // The input frontier at zero justifies messages from two onward.
+        shared1.borrow_mut().produceds[0].update(2, 1);
+        step(&mut worker, &mut subgraph, &[index1]);
+        assert_eq!(subgraph.audit_subscope(index1), Ok(()));
+
+        // A message at one is earlier than the summary allows.
+        shared1.borrow_mut().produceds[0].update(1, 1);
+        step(&mut worker, &mut subgraph, &[index1]);
+        let report = subgraph.audit_subscope(index1).unwrap_err();
+        assert!(report.contains("message at 1 on output 0"));
+    }
+
+    #[test]
+    fn dead_scopes_filter() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        // A source feeds a map and a filter, which both feed a sink.
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (source, shared0) = Puppet::add(builder, 0, 2, 0);
+            let (map, shared1) = Puppet::add(builder, 1, 1, 0);
+            let (filter, shared2) = Puppet::add(builder, 1, 1, 0);
+            let (sink, shared3) = Puppet::add(builder, 2, 0, 0);
+            builder.connect(Source::new(source, 0), Target::new(map, 0));
+            builder.connect(Source::new(source, 1), Target::new(filter, 0));
+            builder.connect(Source::new(map, 0), Target::new(sink, 0));
+            builder.connect(Source::new(filter, 0), Target::new(sink, 1));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            shared0.borrow_mut().internals[1].update(0, 1);
+            children.extend(vec![(source, shared0), (map, shared1), (filter, shared2), (sink, shared3)]);
+        });
+        let indices = children.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+
+        for round in 0 .. 3 {
+            children[0].1.borrow_mut().produceds[0].update(round, 2);
+            children[0].1.borrow_mut().produceds[1].update(round, 2);
+            children[1].1.borrow_mut().consumeds[0].update(round, 2);
+            children[1].1.borrow_mut().produceds[0].update(round, 2);
+            children[2].1.borrow_mut().consumeds[0].update(round, 2);
+            children[3].1.borrow_mut().consumeds[0].update(round, 2);
+            step(&mut worker, &mut subgraph, &indices);
+        }
+
+        // The filter consumed everything and produced nothing; the sink has no outputs.
+        assert_eq!(subgraph.dead_scopes(100), vec![]);
+        assert_eq!(subgraph.dead_scopes(6), vec![indices[2]]);
+    }
+
+    #[test]
+    fn replicas_progress_equals() {
+
+        let mut workers = [Worker::new(Thread::new()), Worker::new(Thread::new())];
+
+        // Two replicas of a chain of two children.
+        let mut replicas = workers.iter_mut().map(|worker| {
+            let mut children = Vec::new();
+            let subgraph = build(worker, |builder| {
+                let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+                let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+                builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+                shared0.borrow_mut().internals[0].update(0, 1);
+                children.push((index0, shared0));
+                children.push((index1, shared1));
+            });
+            (subgraph, children)
+        }).collect::<Vec<_>>();
+
+        assert!(replicas[0].0.progress_equals(&replicas[1].0));
+
+        // Drive both replicas identically, in lockstep.
+        for round in 0 .. 5 {
+            for ((subgraph, children), worker) in replicas.iter_mut().zip(workers.iter_mut()) {
+                let (index0, shared0) = &children[0];
+                let (index1, shared1) = &children[1];
+                shared0.borrow_mut().produceds[0].update(round, 2);
+                shared0.borrow_mut().internals[0].update(round, -1);
+                shared0.borrow_mut().internals[0].update(round + 1, 1);
+                if round > 0 { shared1.borrow_mut().consumeds[0].update(round - 1, 2); }
+                step(worker, subgraph, &[*index0, *index1]);
+            }
+            assert!(replicas[0].0.progress_equals(&replicas[1].0));
+        }
+
+        // Advance the first replica further, and have the second adopt its progress.
+        {
+            let (subgraph, children) = &mut replicas[0];
+            children[0].1.borrow_mut().internals[0].update(5, -1);
+            children[1].1.borrow_mut().consumeds[0].update(4, 2);
+            step(&mut workers[0], subgraph, &[children[0].0, children[1].0]);
+        }
+        assert!(!replicas[0].0.progress_equals(&replicas[1].0));
+
+        let (first, second) = replicas.split_at_mut(1);
+        let (first, second) = (&mut first[0], &mut second[0]);
+        second.1[1].1.borrow_mut().frontiers[0].clear();
+        second.0.adopt_progress(&first.0);
+        assert!(first.0.progress_equals(&second.0));
+
+        // The adopted progress is presented to the children of the second replica.
+        let changes = second.1[1].1.borrow_mut().frontiers[0].drain().collect::<Vec<_>>();
+        assert_eq!(changes, vec![(4, -1)]);
+        assert_eq!(second.0.pending_work(), 0);
+    }
+
+    #[test]
+    fn restore_progress_added_scope() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        // The same subgraph, with and without an additional child.
+        let mut subgraphs = Vec::new();
+        for &extra in [false, true].iter() {
+            let subgraph = build(&mut worker, |builder| {
+                let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+                let (index1, _) = Puppet::add(builder, 1, 0, 0);
+                builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+                if !extra {
+                    shared0.borrow_mut().internals[0].update(3, 1);
+                    shared0.borrow_mut().produceds[0].update(3, 2);
+                }
+                else {
+                    let (index2, _) = Puppet::add(builder, 1, 0, 0);
+                    builder.connect(Source::new(index0, 0), Target::new(index2, 0));
+                }
+            });
+            subgraphs.push(subgraph);
+        }
+        let mut upgraded = subgraphs.pop().unwrap();
+        let original = subgraphs.pop().unwrap();
+
+        let checkpoint = original.checkpoint_progress();
+        assert_eq!(checkpoint.scopes[0].2, vec![vec![(3, 1)]]);
+        assert_eq!(checkpoint.scopes[1].1, vec![vec![(3, 2)]]);
+
+        assert_eq!(upgraded.restore_progress(&checkpoint), Ok(()));
+        let restored = upgraded.checkpoint_progress();
+        assert_eq!(&restored.scopes[.. 2], &checkpoint.scopes[..]);
+        assert_eq!(restored.scopes[2], ("Puppet".to_owned(), vec![vec![]], vec![]));
+        assert_eq!(upgraded.pending_work(), 3);
+
+        // The original subgraph has no place for the upgraded subgraph's additional child.
+        let mut original = original;
+        assert!(original.restore_progress(&restored).is_err());
+        assert_eq!(original.checkpoint_progress(), checkpoint);
+    }
+
+    #[test]
+    fn combined_probe_both_outputs() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            for _ in 0 .. 2 {
+                let output = builder.new_output();
+                let (index, shared) = Puppet::add(builder, 0, 1, 0);
+                builder.connect(Source::new(index, 0), Target::new(0, output.port));
+                shared.borrow_mut().internals[0].update(0, 1);
+                children.push((index, shared));
+            }
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        let (index0, shared0) = children.pop().unwrap();
+
+        let probe = subgraph.combined_probe(&[0, 1]);
+        assert!(probe.less_equal(&0));
+
+        // The first output advancing alone does not complete times for the probe.
+        shared0.borrow_mut().internals[0].update(0, -1);
+        shared0.borrow_mut().internals[0].update(5, 1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert!(probe.less_equal(&3));
+
+        // Once both outputs have advanced, the probe is at the lesser of the two.
+        shared1.borrow_mut().internals[0].update(0, -1);
+        shared1.borrow_mut().internals[0].update(10, 1);
+        step(&mut worker, &mut subgraph, &[index1]);
+        assert!(!probe.less_equal(&3));
+        assert!(probe.less_equal(&5));
+        assert!(!probe.less_than(&5));
+
+        shared0.borrow_mut().internals[0].update(5, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert!(!probe.less_equal(&9));
+        assert!(!probe.done());
+
+        shared1.borrow_mut().internals[0].update(10, -1);
+        step(&mut worker, &mut subgraph, &[index1]);
+        assert!(probe.done());
+    }
+
+    #[test]
+    fn detect_leaks_lagging() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 1, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+            children.push((index1, shared1));
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        let (index0, shared0) = children.pop().unwrap();
+
+        // The second child retains a capability at one, while its input advances to twenty.
+        shared1.borrow_mut().internals[0].update(1, 1);
+        shared0.borrow_mut().internals[0].update(0, -1);
+        shared0.borrow_mut().internals[0].update(20, 1);
+        step(&mut worker, &mut subgraph, &[index0, index1]);
+
+        assert_eq!(subgraph.detect_leaks(5), vec![(index1, 0, 1)]);
+        assert_eq!(subgraph.detect_leaks(19), vec![]);
+    }
+
+    #[test]
+    fn backpressure_pauses_and_resumes() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+            children.push((index1, shared1));
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        let (index0, shared0) = children.pop().unwrap();
+
+        subgraph.set_backpressure(index1, 0, 5);
+
+        // A fast source produces three messages a round, while a slow consumer consumes one.
+        let mut paused = Vec::new();
+        for _round in 0 .. 12 {
+            let pause = subgraph.backpressured();
+            if !pause { shared0.borrow_mut().produceds[0].update(0, 3); }
+            if subgraph.pending_work() > 1 { shared1.borrow_mut().consumeds[0].update(0, 1); }
+            step(&mut worker, &mut subgraph, &[index0, index1]);
+            assert!(subgraph.pending_work() - 1 <= 5 + 3);
+            paused.push(pause);
+        }
+
+        // Outstanding messages: 2, 4, 6 (pause) 5 (pause) 4, 6 (pause) 5 (pause) 4, ...
+        assert_eq!(paused, vec![false, false, false, true, true, false, true, true, false, true, true, false]);
+
+        // Raising the watermark resumes the source.
+        subgraph.set_backpressure(index1, 0, 100);
+        assert!(!subgraph.backpressured());
+    }
+
+    #[test]
+    fn late_output_reachability() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::<usize, usize>::new_from(0, Vec::new(), None, "Test");
+        let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+        let (index, shared) = Puppet::add(&mut builder, 1, 1, 1);
+        builder.connect(Source::new(0, input.port), Target::new(index, 0));
+        shared.borrow_mut().internals[0].update(0, 1);
+
+        // The output is allocated only after the child and its input edge.
+        let output = builder.new_output();
+        builder.connect(Source::new(index, 0), Target::new(0, output.port));
+
+        let mut subgraph = builder.build(&mut worker);
+        let (summary, progress) = subgraph.get_internal_summary();
+
+        assert_eq!(summary, vec![vec![Antichain::from_elem(1)]]);
+        assert_eq!(progress.borrow_mut().internals[output.port].drain().collect::<Vec<_>>(), vec![(0, 1)]);
+    }
+
+    #[test]
+    fn frontier_quantum_rounds() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let output = builder.new_output();
+            builder.connect(Source::new(index0, 0), Target::new(0, output.port));
+            shared0.borrow_mut().internals[0].update(3, 1);
+            children.push((index0, shared0));
+        });
+        let (index0, shared0) = children.pop().unwrap();
+
+        // The initial capability at three is re-expressed at zero.
+        subgraph.set_frontier_quantum(10);
+        let mut initial = subgraph.shared_progress.borrow_mut().internals[0].drain().collect::<Vec<_>>();
+        initial.sort();
+        assert_eq!(initial, vec![(0, 1)]);
+
+        // Advance the child's capability one step at a time, from three to twenty-five.
+        let mut reported = Vec::new();
+        for time in 3 .. 26 {
+            shared0.borrow_mut().internals[0].update(time, -1);
+            shared0.borrow_mut().internals[0].update(time + 1, 1);
+            step(&mut worker, &mut subgraph, &[index0]);
+            reported.extend(subgraph.shared_progress.borrow_mut().internals[0].drain());
+        }
+
+        let mut frontier = ChangeBatch::new();
+        frontier.extend(reported.iter().cloned());
+        assert_eq!(frontier.into_inner(), vec![(0, -1), (20, 1)]);
+        assert_eq!(reported.iter().filter(|x| x.1 > 0).map(|x| x.0).collect::<Vec<_>>(), vec![10, 20]);
+
+        // Releasing the capability empties the output frontier.
+        shared0.borrow_mut().internals[0].update(26, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(subgraph.shared_progress.borrow_mut().internals[0].drain().collect::<Vec<_>>(), vec![(20, -1)]);
+    }
+
+    #[test]
+    fn trim_buffers_after_burst() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+            children.push((index1, shared1));
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        let (index0, shared0) = children.pop().unwrap();
+
+        // A burst of messages at many distinct times, all consumed in the next step.
+        for time in 0 .. 1000 { shared0.borrow_mut().produceds[0].update(time, 1); }
+        step(&mut worker, &mut subgraph, &[index0]);
+        for time in 0 .. 1000 { shared1.borrow_mut().consumeds[0].update(time, 1); }
+        step(&mut worker, &mut subgraph, &[index1]);
+        assert_eq!(subgraph.pending_work(), 1);
+
+        let capacity = |subgraph: &Subgraph<usize, usize>| {
+            subgraph.local_pointstamp.unstable_internal_updates().capacity() +
+            subgraph.final_pointstamp.unstable_internal_updates().capacity()
+        };
+
+        assert!(capacity(&subgraph) >= 1000);
+        subgraph.trim_buffers(16);
+        assert!(capacity(&subgraph) <= 32);
+
+        // Progress tracking is unaffected.
+        shared0.borrow_mut().internals[0].update(0, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(subgraph.pending_work(), 0);
+    }
+
+    #[test]
+    fn run_recording_frontiers() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::<usize, usize>::new_from(0, Vec::new(), None, "Test");
+        let index = Counter::add(&mut builder, &mut worker, 5);
+        let output = builder.new_output();
+        builder.connect(Source::new(index, 0), Target::new(0, output.port));
+        worker.activations().borrow_mut().activate(&[0, index]);
+
+        let mut subgraph = builder.build(&mut worker);
+        subgraph.get_internal_summary();
+        subgraph.set_external_summary();
+
+        let recording = subgraph.run_recording(100);
+        assert!(recording.len() < 100);
+
+        // Frontiers advance monotonically through each iteration, until the output is complete.
+        for ticks in recording.windows(2) {
+            let (before, after) = (&ticks[0][0], &ticks[1][0]);
+            assert!(after.iter().all(|t| before.iter().any(|s| s <= t)));
+        }
+        let mut distinct = recording.iter().map(|tick| tick[0].clone()).collect::<Vec<_>>();
+        distinct.dedup();
+        assert_eq!(distinct, vec![vec![0], vec![1], vec![2], vec![3], vec![4], vec![]]);
+    }
+
+    #[test]
+    fn check_loop_closure_flags() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        // A loop whose feedback leads into its body, with and without the back-edge.
+        for &closed in [false, true].iter() {
+            let mut feedback = 0;
+            let subgraph = build(&mut worker, |builder| {
+                let (index0, _) = Puppet::add(builder, 1, 1, 1);
+                let (index1, _) = Puppet::add(builder, 1, 1, 0);
+                builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+                if closed {
+                    builder.connect(Source::new(index1, 0), Target::new(index0, 0));
+                }
+                feedback = index0;
+            });
+
+            let expected = if closed { Vec::new() } else { vec![feedback] };
+            assert_eq!(subgraph.check_loop_closure(), expected);
+        }
+    }
+
+    #[test]
+    fn check_loop_closure_with_output() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        // A loop whose body also leads to a scope output, with and without the back-edge.
+        for &closed in [false, true].iter() {
+            let mut feedback = 0;
+            let subgraph = build(&mut worker, |builder| {
+                let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+                let output = builder.new_output();
+                let (index0, _) = Puppet::add(builder, 1, 1, 1);
+                let (index1, _) = Puppet::add(builder, 1, 1, 0);
+                builder.connect(Source::new(0, input.port), Target::new(index1, 0));
+                builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+                builder.connect(Source::new(index1, 0), Target::new(0, output.port));
+                if closed {
+                    builder.connect(Source::new(index1, 0), Target::new(index0, 0));
+                }
+                feedback = index0;
+            });
+
+            let expected = if closed { Vec::new() } else { vec![feedback] };
+            assert_eq!(subgraph.check_loop_closure(), expected);
+        }
+    }
+
+    #[test]
+    fn output_floor_clamps() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let output = builder.new_output();
+            builder.connect(Source::new(index0, 0), Target::new(0, output.port));
+            shared0.borrow_mut().internals[0].update(2, 1);
+            children.push((index0, shared0));
+        });
+        let (index0, shared0) = children.pop().unwrap();
+
+        let drain = |subgraph: &mut Subgraph<usize, usize>| {
+            let mut changes = subgraph.shared_progress.borrow_mut().internals[0].drain().collect::<Vec<_>>();
+            changes.sort();
+            changes
+        };
+        assert_eq!(drain(&mut subgraph), vec![(2, 1)]);
+
+        // The capability at two is reported at the floor.
+        subgraph.set_output_floor(0, 5);
+        assert_eq!(drain(&mut subgraph), vec![(2, -1), (5, 1)]);
+        assert!(subgraph.output_capabilities[0].less_equal(&2));
+
+        // Advancing within the floor changes nothing, and beyond it is reported as is.
+        shared0.borrow_mut().internals[0].update(2, -1);
+        shared0.borrow_mut().internals[0].update(4, 1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(drain(&mut subgraph), vec![]);
+
+        shared0.borrow_mut().internals[0].update(4, -1);
+        shared0.borrow_mut().internals[0].update(13, 1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(drain(&mut subgraph), vec![(5, -1), (13, 1)]);
+
+        // Rounding applies before flooring, and changes to either re-express the capability.
+        subgraph.set_frontier_quantum(10);
+        assert_eq!(drain(&mut subgraph), vec![(10, 1), (13, -1)]);
+        subgraph.set_frontier_quantum(4);
+        assert_eq!(drain(&mut subgraph), vec![(10, -1), (12, 1)]);
+
+        shared0.borrow_mut().internals[0].update(13, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(drain(&mut subgraph), vec![(12, -1)]);
+    }
+
+    #[test]
+    fn reorder_window_trails() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let output = builder.new_output();
+            builder.connect(Source::new(index0, 0), Target::new(0, output.port));
+            shared0.borrow_mut().internals[0].update(10, 1);
+            children.push((index0, shared0));
+        });
+        let (index0, shared0) = children.pop().unwrap();
+
+        let drain = |subgraph: &mut Subgraph<usize, usize>| {
+            let mut changes = subgraph.shared_progress.borrow_mut().internals[0].drain().collect::<Vec<_>>();
+            changes.sort();
+            changes
+        };
+        assert_eq!(drain(&mut subgraph), vec![(10, 1)]);
+
+        // The capability at ten is reported three earlier.
+        subgraph.set_reorder_window(0, 3);
+        assert_eq!(drain(&mut subgraph), vec![(7, 1), (10, -1)]);
+        assert_eq!(subgraph.output_complete_through(0), Some(6));
+
+        // Advancing the capability advances the report, still three behind.
+        shared0.borrow_mut().internals[0].update(10, -1);
+        shared0.borrow_mut().internals[0].update(12, 1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(drain(&mut subgraph), vec![(7, -1), (9, 1)]);
+
+        // Times within the window of the least timestamp are reported at it.
+        subgraph.set_reorder_window(0, 20);
+        assert_eq!(drain(&mut subgraph), vec![(0, 1), (9, -1)]);
+        assert_eq!(subgraph.output_complete_through(0), None);
+
+        shared0.borrow_mut().internals[0].update(12, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(drain(&mut subgraph), vec![(0, -1)]);
+    }
+
+    #[test]
+    fn output_floors_fanned_out() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let output0 = builder.new_output();
+            let output1 = builder.new_output();
+            let (index, shared) = Puppet::add(builder, 0, 1, 0);
+            builder.connect(Source::new(index, 0), Target::new(0, output0.port));
+            builder.connect(Source::new(index, 0), Target::new(0, output1.port));
+            shared.borrow_mut().internals[0].update(3, 1);
+            children.push((index, shared));
+        });
+        let (index, shared) = children.pop().unwrap();
+
+        let drain = |subgraph: &mut Subgraph<usize, usize>| {
+            subgraph.pull_progress().internals
+        };
+        subgraph.set_output_floor(0, 0);
+        subgraph.set_output_floor(1, 100);
+        assert_eq!(drain(&mut subgraph), vec![vec![(3, 1)], vec![(100, 1)]]);
+
+        // The shared capability advances, and each output reports it subject to its own floor.
+        shared.borrow_mut().internals[0].update(3, -1);
+        shared.borrow_mut().internals[0].update(50, 1);
+        step(&mut worker, &mut subgraph, &[index]);
+        assert_eq!(drain(&mut subgraph), vec![vec![(3, -1), (50, 1)], vec![]]);
+
+        shared.borrow_mut().internals[0].update(50, -1);
+        shared.borrow_mut().internals[0].update(150, 1);
+        step(&mut worker, &mut subgraph, &[index]);
+        assert_eq!(drain(&mut subgraph), vec![vec![(50, -1), (150, 1)], vec![(100, -1), (150, 1)]]);
+    }
+
+    #[test]
+    fn in_flight_at_remainder() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+            children.push((index1, shared1));
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        let (index0, shared0) = children.pop().unwrap();
+
+        shared0.borrow_mut().produceds[0].update(2, 5);
+        shared0.borrow_mut().produceds[0].update(3, 3);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(subgraph.in_flight_at(&2), 5);
+
+        // Partially consume the messages at time two.
+        shared1.borrow_mut().consumeds[0].update(2, 2);
+        step(&mut worker, &mut subgraph, &[index1]);
+        assert_eq!(subgraph.in_flight_at(&2), 3);
+        assert_eq!(subgraph.in_flight_at(&3), 3);
+        assert_eq!(subgraph.in_flight_at(&0), 0);
+    }
+
+    #[test]
+    fn outstanding_by_time_sorted() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+            children.push((index1, shared1));
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        let (index0, shared0) = children.pop().unwrap();
+
+        shared0.borrow_mut().produceds[0].update(4, 1);
+        shared0.borrow_mut().produceds[0].update(2, 5);
+        shared0.borrow_mut().produceds[0].update(3, 3);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(subgraph.outstanding_by_time(index1, 0), vec![(2, 5), (3, 3), (4, 1)]);
+
+        // Consumed messages are deducted, and times without messages omitted.
+        shared1.borrow_mut().consumeds[0].update(2, 2);
+        shared1.borrow_mut().consumeds[0].update(4, 1);
+        step(&mut worker, &mut subgraph, &[index1]);
+        assert_eq!(subgraph.outstanding_by_time(index1, 0), vec![(2, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn simulate_input_advance_matches() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (index0, shared0) = Puppet::add(builder, 1, 1, 1);
+            let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index0, 0));
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            children.push((index0, shared0));
+            children.push((index1, shared1));
+        });
+        let (index1, shared1) = children.pop().unwrap();
+        let (index0, shared0) = children.pop().unwrap();
+
+        let drain = |shared: &Rc<RefCell<SharedProgress<usize>>>, index: usize| {
+            shared.borrow_mut().frontiers[0].drain().map(|(t, d)| (Target::new(index, 0), t, d)).collect::<Vec<_>>()
+        };
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        drain(&shared0, index0);
+        drain(&shared1, index1);
+
+        let simulated = subgraph.simulate_input_advance(0, 3);
+        let mut expected = vec![
+            (Target::new(index0, 0), 0, -1),
+            (Target::new(index0, 0), 3, 1),
+            (Target::new(index1, 0), 1, -1),
+            (Target::new(index1, 0), 4, 1),
+        ];
+        expected.sort();
+        assert_eq!(simulated, expected);
+
+        // The simulation leaves the subgraph untouched, so may be repeated.
+        assert!(drain(&shared0, index0).is_empty());
+        assert!(drain(&shared1, index1).is_empty());
+        assert_eq!(subgraph.simulate_input_advance(0, 3), simulated);
+
+        // Applying the advance produces the simulated changes.
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, -1);
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(3, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        let mut applied = drain(&shared0, index0);
+        applied.extend(drain(&shared1, index1));
+        applied.sort();
+        assert_eq!(applied, simulated);
+    }
+
+    #[test]
+    fn seal_epoch_advances_inputs() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (index0, shared0) = Puppet::add(builder, 1, 1, 1);
+            let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index0, 0));
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            children.push(shared0);
+            children.push(shared1);
+        });
+        let shared1 = children.pop().unwrap();
+        let shared0 = children.pop().unwrap();
+
+        let drain = |shared: &Rc<RefCell<SharedProgress<usize>>>| {
+            let mut changes = shared.borrow_mut().frontiers[0].drain().collect::<Vec<_>>();
+            changes.sort();
+            changes
+        };
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert_eq!(drain(&shared0), vec![(0, 1)]);
+        assert_eq!(drain(&shared1), vec![(1, 1)]);
+
+        // Sealing advances the input frontier, and the frontiers downstream of it.
+        subgraph.seal_epoch(5);
+        assert_eq!(drain(&shared0), vec![(0, -1), (5, 1)]);
+        assert_eq!(drain(&shared1), vec![(1, -1), (6, 1)]);
+
+        // The parent advancing the frontier within the epoch changes nothing.
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, -1);
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(3, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert!(drain(&shared0).is_empty());
+        assert!(drain(&shared1).is_empty());
+
+        // Advancing beyond the epoch is applied as is.
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(3, -1);
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(7, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert_eq!(drain(&shared0), vec![(5, -1), (7, 1)]);
+        assert_eq!(drain(&shared1), vec![(6, -1), (8, 1)]);
+    }
+
+    #[test]
+    fn push_external_progress_all_matches() {
+
+        // Connects three inputs to one child, which feeds another, and applies `changes` to the inputs
+        // together or one at a time, returning the frontier changes at both children.
+        fn run(changes: &[Vec<(usize, i64)>], together: bool) -> Vec<Vec<(usize, i64)>> {
+
+            let mut worker = Worker::new(Thread::new());
+
+            let mut children = Vec::new();
+            let mut subgraph = build(&mut worker, |builder| {
+                let (index0, shared0) = Puppet::add(builder, 3, 1, 1);
+                let (index1, shared1) = Puppet::add(builder, 1, 0, 0);
+                for port in 0 .. 3 {
+                    let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+                    builder.connect(Source::new(0, input.port), Target::new(index0, port));
+                }
+                builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+                children.push(shared0);
+                children.push(shared1);
+            });
+
+            if together {
+                subgraph.push_external_progress_all(changes.to_vec());
+            }
+            else {
+                for (input, changes) in changes.iter().enumerate() {
+                    let mut per_input = vec![Vec::new(); input];
+                    per_input.push(changes.clone());
+                    subgraph.push_external_progress_all(per_input);
+                }
+            }
+
+            children
+                .iter()
+                .flat_map(|shared| shared.borrow_mut().frontiers.iter_mut().map(|x| x.drain().collect()).collect::<Vec<_>>())
+                .collect()
+        }
+
+        let changes = vec![vec![(0, 1)], vec![(2, 1)], vec![(1, 1), (4, 1)]];
+        let together = run(&changes, true);
+        assert_eq!(together, run(&changes, false));
+        assert_eq!(together, vec![vec![(0, 1)], vec![(2, 1)], vec![(1, 1)], vec![(1, 1)]]);
+    }
+
+    #[test]
+    fn input_closed_through_sealed() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (index, _shared) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index, 0));
+        });
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(2, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert_eq!(subgraph.input_closed_through(0), vec![2]);
+
+        subgraph.seal_epoch(5);
+        assert_eq!(subgraph.input_closed_through(0), vec![5]);
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(2, -1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert!(subgraph.input_closed_through(0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "before sealed epoch")]
+    fn seal_epoch_rejects_messages() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let counts = Rc::new(RefCell::new(ChangeBatch::new()));
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(counts.clone());
+            let (index, _shared) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index, 0));
+        });
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        subgraph.seal_epoch(5);
+
+        counts.borrow_mut().update(3, 1);
+        step(&mut worker, &mut subgraph, &[]);
+    }
+
+    #[test]
+    fn global_frontier_drains() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (index, shared) = Puppet::add(builder, 1, 1, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index, 0));
+            shared.borrow_mut().internals[0].update(3, 1);
+            children.push((index, shared));
+        });
+        let (index, shared) = children.pop().unwrap();
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert_eq!(subgraph.global_frontier(), vec![0]);
+
+        // Sealing the input leaves the capability the least work.
+        subgraph.seal_epoch(5);
+        assert_eq!(subgraph.global_frontier(), vec![3]);
+
+        // Releasing the capability leaves the sealed input.
+        shared.borrow_mut().internals[0].update(3, -1);
+        step(&mut worker, &mut subgraph, &[index]);
+        assert_eq!(subgraph.global_frontier(), vec![5]);
+
+        // Closing the input leaves no work at all.
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, -1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert!(subgraph.global_frontier().is_empty());
+    }
+
+    #[test]
+    fn activate_when_gates_scheduling() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (index, _shared, scheduled) = Puppet::add_counted(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index, 0));
+            children.push((index, scheduled));
+        });
+        let (index, scheduled) = children.pop().unwrap();
+
+        subgraph.activate_when(index, |frontier| frontier.iter().all(|time| *time >= 10));
+
+        // Neither activations nor frontier changes schedule the child before its frontier reaches ten.
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, 1);
+        step(&mut worker, &mut subgraph, &[index]);
+        for time in 1 .. 10 {
+            subgraph.shared_progress.borrow_mut().frontiers[0].update(time - 1, -1);
+            subgraph.shared_progress.borrow_mut().frontiers[0].update(time, 1);
+            step(&mut worker, &mut subgraph, &[index]);
+        }
+        assert_eq!(scheduled.get(), 0);
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(9, -1);
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(10, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert!(scheduled.get() > 0);
+    }
+
+    #[test]
+    fn scope_notify_override() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (index, _shared, scheduled) = Puppet::add_counted(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index, 0));
+            children.push((index, scheduled));
+        });
+        let (index, scheduled) = children.pop().unwrap();
+
+        // A notified child is scheduled in response to its input frontier changing.
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        let before = scheduled.get();
+        assert!(before > 0);
+
+        // Once notification is off, changes to its input frontier no longer schedule it.
+        subgraph.set_scope_notify(index, false);
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, -1);
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(5, 1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert_eq!(scheduled.get(), before);
+    }
+
+    #[test]
+    fn paused_scope_backlog() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::new_from(0, Vec::new(), None, "Test");
+        let (index0, shared0) = Puppet::add(&mut builder, 0, 1, 0);
+        let (index1, shared1) = Puppet::add(&mut builder, 1, 1, 0);
+        let (index2, shared2) = Puppet::add(&mut builder, 1, 0, 0);
+        builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+        builder.connect(Source::new(index1, 0), Target::new(index2, 0));
+        shared0.borrow_mut().internals[0].update(0, 1);
+        let mut subgraph = builder.build(&mut worker);
+        subgraph.get_internal_summary();
+        subgraph.set_external_summary();
+
+        let frontier = |shared: &Rc<RefCell<SharedProgress<usize>>>| {
+            let mut changes = shared.borrow_mut().frontiers[0].drain().collect::<Vec<_>>();
+            changes.sort();
+            changes
+        };
+        step(&mut worker, &mut subgraph, &[]);
+        assert_eq!(frontier(&shared1), vec![(0, 1)]);
+        assert_eq!(frontier(&shared2), vec![(0, 1)]);
+
+        // The middle child is paused while messages are sent to it and the source completes.
+        subgraph.pause_scope(index1);
+        shared0.borrow_mut().produceds[0].update(0, 5);
+        shared0.borrow_mut().internals[0].update(0, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        shared1.borrow_mut().consumeds[0].update(0, 5);
+        step(&mut worker, &mut subgraph, &[index1]);
+        assert_eq!(subgraph.in_flight_at(&0), 5);
+        assert_eq!(frontier(&shared2), vec![]);
+
+        // Once resumed, the middle child reports its backlog consumed and the frontier advances.
+        subgraph.resume_scope(index1);
+        step(&mut worker, &mut subgraph, &[]);
+        assert_eq!(subgraph.in_flight_at(&0), 0);
+        assert_eq!(frontier(&shared1), vec![(0, -1)]);
+        assert_eq!(frontier(&shared2), vec![(0, -1)]);
+    }
+
+    #[test]
+    fn counter_converges() {
+
+        use crate::progress::testing::ProgressAssert;
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::<usize, usize>::new_from(0, Vec::new(), None, "Test");
+        let index = Counter::add(&mut builder, &mut worker, 3);
+        let output = builder.new_output();
+        builder.connect(Source::new(index, 0), Target::new(0, output.port));
+        worker.activations().borrow_mut().activate(&[0, index]);
+
+        let mut subgraph = builder.build(&mut worker);
+        let mut progress = ProgressAssert::new(0, 1);
+        let (_, shared) = subgraph.get_internal_summary();
+        progress.observe(shared.borrow_mut().report());
+        subgraph.set_external_summary();
+        progress.assert_output_frontier(0, &[0]);
+
+        // The reported progress accumulates to the recorded frontiers, until the output is complete.
+        let mut ticks = 0;
+        while let Some(frontiers) = subgraph.run_recording(1).pop() {
+            progress.observe(subgraph.pull_progress());
+            progress.assert_output_frontier(0, &frontiers[0]);
+            if frontiers[0].is_empty() { break; }
+            ticks += 1;
+            assert!(ticks < 100);
+        }
+        progress.assert_quiescent();
+    }
+
+    #[test]
+    fn run_and_collect_outputs_totals() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        // Two mappers feed a reducer, which produces at the scope output.
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let output = builder.new_output();
+            let (map0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let (map1, shared1) = Puppet::add(builder, 0, 1, 0);
+            let (reduce, shared2) = Puppet::add(builder, 2, 1, 0);
+            builder.connect(Source::new(map0, 0), Target::new(reduce, 0));
+            builder.connect(Source::new(map1, 0), Target::new(reduce, 1));
+            builder.connect(Source::new(reduce, 0), Target::new(0, output.port));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            shared1.borrow_mut().internals[0].update(0, 1);
+            children.push((map0, shared0));
+            children.push((map1, shared1));
+            children.push((reduce, shared2));
+        });
+        let (reduce, shared2) = children.pop().unwrap();
+        let (map1, shared1) = children.pop().unwrap();
+        let (map0, shared0) = children.pop().unwrap();
+
+        // The mappers produce records at two times and complete.
+        for shared in [&shared0, &shared1].iter() {
+            let mut shared = shared.borrow_mut();
+            shared.produceds[0].update(0, 3);
+            shared.produceds[0].update(1, 2);
+            shared.internals[0].update(0, -1);
+        }
+        // The reducer consumes them, and produces one total for each time, twice at time one.
+        shared2.borrow_mut().consumeds[0].update(0, 3);
+        shared2.borrow_mut().consumeds[0].update(1, 2);
+        shared2.borrow_mut().consumeds[1].update(0, 3);
+        shared2.borrow_mut().consumeds[1].update(1, 2);
+        shared2.borrow_mut().produceds[0].update(1, 1);
+        shared2.borrow_mut().produceds[0].update(0, 1);
+        shared2.borrow_mut().produceds[0].update(1, 1);
+
+        {   // Scoped, to allow borrow to drop.
+            let activations = worker.activations();
+            let mut activations = activations.borrow_mut();
+            for child in [map0, map1, reduce].iter() {
+                activations.activate(&[0, *child]);
+            }
+        }
+        assert_eq!(subgraph.run_and_collect_outputs(100), vec![vec![(0, 1), (1, 2)]]);
+    }
+
+    #[test]
+    fn buffers_retained() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::<usize, usize>::new_from(0, Vec::new(), None, "Test");
+        let index = Counter::add(&mut builder, &mut worker, 100);
+        let output = builder.new_output();
+        builder.connect(Source::new(index, 0), Target::new(0, output.port));
+        worker.activations().borrow_mut().activate(&[0, index]);
+
+        let mut subgraph = builder.build(&mut worker);
+        subgraph.get_internal_summary();
+        subgraph.set_external_summary();
+
+        let capacities = |subgraph: &Subgraph<usize, usize>| {
+            (
+                subgraph.local_pointstamp.unstable_internal_updates().capacity(),
+                subgraph.final_pointstamp.unstable_internal_updates().capacity(),
+                subgraph.temp_active.capacity(),
+            )
+        };
+
+        // Once warmed up, each scheduling reuses the same buffers.
+        subgraph.run_recording(5);
+        let warm = capacities(&subgraph);
+        assert!(warm.1 > 0);
+        for _ in 0 .. 50 {
+            subgraph.run_recording(1);
+            assert_eq!(capacities(&subgraph), warm);
+        }
+    }
+
+    #[test]
+    fn last_frontier_changes_recorded() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (index, shared) = Puppet::add(builder, 1, 1, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index, 0));
+            shared.borrow_mut().internals[0].update(0, 1);
+            children.push(index);
+        });
+        let index = children.pop().unwrap();
+        subgraph.record_frontier_changes(true);
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, 1);
+        subgraph.schedule();
+        assert_eq!(subgraph.last_frontier_changes(index), vec![(0, 0, 1)]);
+
+        // Advancing the input frontier advances the child's frontier.
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(0, -1);
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(3, 1);
+        subgraph.schedule();
+        let mut changes = subgraph.last_frontier_changes(index);
+        changes.sort();
+        assert_eq!(changes, vec![(0, 0, -1), (0, 3, 1)]);
+
+        // Changes are retained only until the next scheduling.
+        subgraph.schedule();
+        assert_eq!(subgraph.last_frontier_changes(index), vec![]);
+    }
+
+    #[test]
+    fn capability_ledger_unreleased() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::new_from(0, Vec::new(), None, "Test");
+        let (index, shared) = Puppet::add(&mut builder, 0, 1, 0);
+        shared.borrow_mut().internals[0].update(0, 1);
+        let mut subgraph = builder.build(&mut worker);
+        subgraph.track_capabilities(true);
+        subgraph.get_internal_summary();
+        subgraph.set_external_summary();
+
+        let output = Location::new_source(index, 0);
+        assert_eq!(subgraph.capability_ledger(), vec![(output, 0, 1)]);
+
+        // The capability at zero is released, and one at three is acquired and not released.
+        shared.borrow_mut().internals[0].update(0, -1);
+        shared.borrow_mut().internals[0].update(3, 1);
+        step(&mut worker, &mut subgraph, &[index]);
+        assert_eq!(subgraph.capability_ledger(), vec![(output, 3, 1)]);
+
+        shared.borrow_mut().internals[0].update(3, -1);
+        step(&mut worker, &mut subgraph, &[index]);
+        assert!(subgraph.capability_ledger().is_empty());
+    }
+
+    #[test]
+    fn frontier_history() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::<usize, usize>::new_from(0, Vec::new(), None, "Test");
+        let index = Counter::add(&mut builder, &mut worker, 5);
+        let output = builder.new_output();
+        builder.connect(Source::new(index, 0), Target::new(0, output.port));
+        worker.activations().borrow_mut().activate(&[0, index]);
+
+        let mut subgraph = builder.build(&mut worker);
+        subgraph.get_internal_summary();
+        subgraph.set_external_summary();
+        assert_eq!(subgraph.frontier_at(0, 0), None);
+
+        subgraph.set_history_depth(3);
+        let recording = subgraph.run_recording(100);
+        let ticks = recording.len();
+        assert!(ticks > 3);
+
+        // The most recent schedulings are retained, and older ones are not.
+        for ticks_ago in 0 .. 3 {
+            assert_eq!(subgraph.frontier_at(0, ticks_ago), Some(recording[ticks - 1 - ticks_ago][0].clone()));
+        }
+        assert_eq!(subgraph.frontier_at(0, 3), None);
+
+        subgraph.set_history_depth(1);
+        assert_eq!(subgraph.frontier_at(0, 0), Some(Vec::new()));
+        assert_eq!(subgraph.frontier_at(0, 1), None);
+    }
+
+    #[test]
+    fn output_complete_through_minimum() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+            let output = builder.new_output();
+            builder.connect(Source::new(index0, 0), Target::new(0, output.port));
+            shared0.borrow_mut().internals[0].update(0, 1);
+            children.push((index0, shared0));
+        });
+        let (index0, shared0) = children.pop().unwrap();
+
+        assert_eq!(subgraph.output_complete_through(0), None);
+
+        // The least of several capabilities determines completion.
+        shared0.borrow_mut().internals[0].update(0, -1);
+        shared0.borrow_mut().internals[0].update(5, 1);
+        shared0.borrow_mut().internals[0].update(9, 1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(subgraph.output_complete_through(0), Some(4));
+
+        shared0.borrow_mut().internals[0].update(5, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(subgraph.output_complete_through(0), Some(8));
+
+        shared0.borrow_mut().internals[0].update(9, -1);
+        step(&mut worker, &mut subgraph, &[index0]);
+        assert_eq!(subgraph.output_complete_through(0), Some(usize::MAX));
+    }
+
+    #[test]
+    fn metrics_text_well_formed() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut children = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let output = builder.new_output();
+            let (index, _) = Puppet::add(builder, 1, 1, 0);
+            builder.connect(Source::new(0, input.port), Target::new(index, 0));
+            builder.connect(Source::new(index, 0), Target::new(0, output.port));
+            children.push(index);
+        });
+        let index = children[0];
+
+        subgraph.shared_progress.borrow_mut().frontiers[0].update(3, 1);
+        step(&mut worker, &mut subgraph, &[]);
+
+        let text = subgraph.metrics_text();
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            let mut parts = line.split(' ');
+            let metric = parts.next().unwrap();
+            let value = parts.next().unwrap();
+            assert!(parts.next().is_none(), "malformed line: {}", line);
+            assert!(metric.starts_with("timely_subgraph_") && metric.ends_with('}'), "malformed metric: {}", line);
+            assert!(metric.contains("{subgraph=\"Test\""), "missing label: {}", line);
+            assert!(value == "+Inf" || value.parse::<i64>().is_ok(), "malformed value: {}", line);
+        }
+
+        assert!(text.contains(&format!("timely_subgraph_outstanding_messages{{subgraph=\"Test\",scope=\"Puppet\",index=\"{}\"}} 0\n", index)));
+        assert!(text.contains("timely_subgraph_output_frontier_min{subgraph=\"Test\",output=\"0\"} 3\n"));
+        assert!(text.contains("timely_subgraph_pending_work{subgraph=\"Test\"} 0\n"));
+        assert!(text.contains("# TYPE timely_subgraph_ticks_total counter\n"));
+    }
+
+    #[test]
+    fn annotate_edge_dot() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut indices = Vec::new();
+        let mut subgraph = build(&mut worker, |builder| {
+            let (index0, _) = Puppet::add(builder, 0, 1, 0);
+            let (index1, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(index0, 0), Target::new(index1, 0));
+            indices.push(index0);
+            indices.push(index1);
+        });
+
+        subgraph.annotate_edge(Source::new(indices[0], 0), Target::new(indices[1], 0), "shuffle".to_owned());
+
+        assert_eq!(subgraph.to_dot(), format!(
+            "digraph \"Test\" {{\n  0 [label=\"External\"];\n  {0} [label=\"Puppet\"];\n  {1} [label=\"Puppet\"];\n  {0} -> {1} [label=\"0 -> 0: shuffle\"];\n}}\n",
+            indices[0], indices[1],
+        ));
+    }
+
+    #[test]
+    fn pull_progress_matches_drain() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        // Two identical subgraphs, whose progress is drained in the two ways.
+        let mut subgraphs = Vec::new();
+        for _ in 0 .. 2 {
+            let mut children = Vec::new();
+            let subgraph = build(&mut worker, |builder| {
+                let (index0, shared0) = Puppet::add(builder, 0, 1, 0);
+                let output = builder.new_output();
+                builder.connect(Source::new(index0, 0), Target::new(0, output.port));
+                shared0.borrow_mut().internals[0].update(1, 1);
+                children.push((index0, shared0));
+            });
+            subgraphs.push((subgraph, children.pop().unwrap()));
+        }
+
+        for time in 1 .. 4 {
+            let mut drained = Vec::new();
+            for (subgraph, (index0, shared0)) in subgraphs.iter_mut() {
+                shared0.borrow_mut().internals[0].update(time, -1);
+                shared0.borrow_mut().internals[0].update(time + 1, 1);
+                step(&mut worker, subgraph, &[*index0]);
+                drained.push(subgraph);
+            }
+            let report = drained[1].pull_progress();
+            let mut shared = drained[0].shared_progress.borrow_mut();
+            assert_eq!(report.consumeds, Vec::<Vec<(usize, i64)>>::new());
+            assert_eq!(report.internals, vec![shared.internals[0].drain().collect::<Vec<_>>()]);
+            assert_eq!(report.produceds, vec![shared.produceds[0].drain().collect::<Vec<_>>()]);
+            assert!(!report.internals[0].is_empty());
+        }
+    }
+
+    #[test]
+    fn unreachable_scopes_flagged() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (a, _) = Puppet::add(builder, 1, 0, 0);
+            let (source, _) = Puppet::add(builder, 0, 1, 0);
+            let (b, _) = Puppet::add(builder, 1, 0, 0);
+            let (disconnected, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(a, 0));
+            builder.connect(Source::new(source, 0), Target::new(b, 0));
+            indices.extend(vec![source, b, disconnected]);
+        });
+
+        assert_eq!(subgraph.unreachable_scopes(false), indices);
+        assert_eq!(subgraph.unreachable_scopes(true), indices[2 ..].to_vec());
+    }
+
+    #[test]
+    fn unreachable_scopes_disconnected_ports() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let input = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            // Only the second input of the upstream child reaches its second output.
+            let summary = vec![
+                vec![Antichain::from_elem(0), Antichain::new()],
+                vec![Antichain::new(), Antichain::from_elem(0)],
+            ];
+            let (a, _) = Puppet::add_summarized(builder, 2, 2, summary);
+            let (b, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input.port), Target::new(a, 0));
+            builder.connect(Source::new(a, 1), Target::new(b, 0));
+            indices.push(b);
+        });
+
+        assert_eq!(subgraph.unreachable_scopes(false), indices);
+        assert_eq!(subgraph.unreachable_scopes(true), indices);
+    }
+
+    #[test]
+    fn scopes_downstream_branching() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let input0 = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let input1 = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let (a, _) = Puppet::add(builder, 1, 2, 0);
+            let (b, _) = Puppet::add(builder, 1, 0, 0);
+            let (c, _) = Puppet::add(builder, 1, 1, 0);
+            let (d, _) = Puppet::add(builder, 1, 0, 0);
+            let (e, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input0.port), Target::new(a, 0));
+            builder.connect(Source::new(a, 0), Target::new(b, 0));
+            builder.connect(Source::new(a, 1), Target::new(c, 0));
+            builder.connect(Source::new(c, 0), Target::new(d, 0));
+            builder.connect(Source::new(0, input1.port), Target::new(e, 0));
+            indices.extend(vec![a, b, c, d, e]);
+        });
+
+        assert_eq!(subgraph.scopes_downstream_of_input(0), indices[.. 4].to_vec());
+        assert_eq!(subgraph.scopes_downstream_of_input(1), indices[4 ..].to_vec());
+    }
+
+    #[test]
+    fn scopes_downstream_diagonal() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut indices = Vec::new();
+        let subgraph = build(&mut worker, |builder| {
+            let input0 = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            let input1 = builder.new_input(Rc::new(RefCell::new(ChangeBatch::new())));
+            // Each input of the middle child reaches only the output of the same index.
+            let summary = vec![
+                vec![Antichain::from_elem(0), Antichain::new()],
+                vec![Antichain::new(), Antichain::from_elem(0)],
+            ];
+            let (a, _) = Puppet::add_summarized(builder, 2, 2, summary);
+            let (b, _) = Puppet::add(builder, 1, 0, 0);
+            let (c, _) = Puppet::add(builder, 1, 0, 0);
+            builder.connect(Source::new(0, input0.port), Target::new(a, 0));
+            builder.connect(Source::new(0, input1.port), Target::new(a, 1));
+            builder.connect(Source::new(a, 0), Target::new(b, 0));
+            builder.connect(Source::new(a, 1), Target::new(c, 0));
+            indices.extend(vec![a, b, c]);
+        });
+
+        assert_eq!(subgraph.scopes_downstream_of_input(0), vec![indices[0], indices[1]]);
+        assert_eq!(subgraph.scopes_downstream_of_input(1), vec![indices[0], indices[2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "scheduled before")]
+    fn schedule_unsealed() {
+
+        let mut worker = Worker::new(Thread::new());
+
+        let mut builder = SubgraphBuilder::<usize, usize>::new_from(0, Vec::new(), None, "Test");
+        Puppet::add(&mut builder, 0, 1, 0);
+        let mut subgraph = builder.build(&mut worker);
+        subgraph.get_internal_summary();
+        subgraph.schedule();
     }
 }
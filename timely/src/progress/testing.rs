@@ -0,0 +1,312 @@
+//! Fixtures for testing implementations of `Operate`.
+//!
+//! Operators communicate their progress through `SharedProgress`, and a parent scope relies on
+//! operators to respect a contract in what they report: they may only acquire capabilities and
+//! produce messages at times they could have obtained from capabilities they hold, from their
+//! input frontiers, or from messages they consume, and no counts may become negative. The
+//! `check_operate_contract` method drives an operator through a sequence of input frontiers and
+//! reports each violation of this contract that it observes.
+//!
+//! The `ProgressAssert` type accumulates the progress an operator reports, and provides assertions
+//! about it whose failures describe the mismatch.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::rc::Rc;
+//! use std::cell::RefCell;
+//!
+//! use timely::scheduling::Schedule;
+//! use timely::progress::{Operate, Antichain};
+//! use timely::progress::frontier::MutableAntichain;
+//! use timely::progress::operate::SharedProgress;
+//! use timely::progress::testing::check_operate_contract;
+//!
+//! // Holds a capability at its input frontier, and optionally produces a stale message.
+//! struct Follower {
+//!     frontier: MutableAntichain<u64>,
+//!     shared: Rc<RefCell<SharedProgress<u64>>>,
+//!     stale: bool,
+//! }
+//!
+//! impl Schedule for Follower {
+//!     fn name(&self) -> &str { "Follower" }
+//!     fn path(&self) -> &[usize] { &[] }
+//!     fn schedule(&mut self) -> bool {
+//!         let mut shared = self.shared.borrow_mut();
+//!         let shared = &mut *shared;
+//!         let internals = &mut shared.internals[0];
+//!         let changes = self.frontier.update_iter(shared.frontiers[0].drain());
+//!         for (time, diff) in changes { internals.update(time, diff); }
+//!         if self.stale && self.frontier.is_empty() { shared.produceds[0].update(0, 1); }
+//!         false
+//!     }
+//! }
+//!
+//! impl Operate<u64> for Follower {
+//!     fn inputs(&self) -> usize { 1 }
+//!     fn outputs(&self) -> usize { 1 }
+//!     fn get_internal_summary(&mut self) -> (Vec<Vec<Antichain<u64>>>, Rc<RefCell<SharedProgress<u64>>>) {
+//!         (vec![vec![Antichain::from_elem(0)]], self.shared.clone())
+//!     }
+//! }
+//!
+//! for &stale in [false, true].iter() {
+//!     let mut follower = Follower {
+//!         frontier: MutableAntichain::new(),
+//!         shared: Rc::new(RefCell::new(SharedProgress::new(1, 1))),
+//!         stale,
+//!     };
+//!     let result = check_operate_contract(&mut follower, &[1, 5, 6, 10], 0);
+//!     assert_eq!(result.is_err(), stale);
+//! }
+//! ```
+
+use crate::progress::{Timestamp, Operate, ChangeBatch};
+use crate::progress::frontier::MutableAntichain;
+use crate::progress::operate::{ProgressReport, summary_justifies};
+
+/// Accumulates the progress reported by an operator, for assertions about its convergence.
+///
+/// Reports drained from the operator, for example by `Subgraph::pull_progress` or
+/// `SharedProgress::report`, are supplied to `observe`. The assertions then concern all progress
+/// observed so far, and panic with a message describing any mismatch.
+///
+/// # Examples
+///
+/// ```
+/// use timely::progress::operate::SharedProgress;
+/// use timely::progress::testing::ProgressAssert;
+///
+/// let mut shared = SharedProgress::<u64>::new(1, 1);
+/// let mut progress = ProgressAssert::new(1, 1);
+///
+/// shared.internals[0].update(0, 1);
+/// shared.consumeds[0].update(0, 3);
+/// progress.observe(shared.report());
+/// progress.assert_output_frontier(0, &[0]);
+/// progress.assert_consumed(0, 0, 3);
+///
+/// shared.internals[0].update(0, -1);
+/// progress.observe(shared.report());
+/// progress.assert_quiescent();
+/// ```
+#[derive(Debug)]
+pub struct ProgressAssert<T: Timestamp> {
+    consumed: Vec<ChangeBatch<T>>,
+    produced: Vec<ChangeBatch<T>>,
+    capabilities: Vec<MutableAntichain<T>>,
+}
+
+impl<T: Timestamp> ProgressAssert<T> {
+    /// Allocates an accumulator for an operator with the given numbers of inputs and outputs.
+    pub fn new(inputs: usize, outputs: usize) -> Self {
+        ProgressAssert {
+            consumed: vec![ChangeBatch::new(); inputs],
+            produced: vec![ChangeBatch::new(); outputs],
+            capabilities: (0 .. outputs).map(|_| MutableAntichain::new()).collect(),
+        }
+    }
+
+    /// Accumulates the progress in `report`.
+    pub fn observe(&mut self, report: ProgressReport<T>) {
+        assert_eq!(report.consumeds.len(), self.consumed.len(), "Report has {} inputs, expected {}", report.consumeds.len(), self.consumed.len());
+        assert_eq!(report.internals.len(), self.capabilities.len(), "Report has {} outputs, expected {}", report.internals.len(), self.capabilities.len());
+        for (consumed, changes) in self.consumed.iter_mut().zip(report.consumeds) {
+            consumed.extend(changes.into_iter());
+        }
+        for (produced, changes) in self.produced.iter_mut().zip(report.produceds) {
+            produced.extend(changes.into_iter());
+        }
+        for (capabilities, changes) in self.capabilities.iter_mut().zip(report.internals) {
+            capabilities.update_iter(changes);
+        }
+    }
+
+    /// Asserts that the frontier of capabilities held for `output` is `expected`, in any order.
+    pub fn assert_output_frontier(&self, output: usize, expected: &[T]) {
+        let mut frontier = self.capabilities[output].frontier().to_vec();
+        let mut expected = expected.to_vec();
+        frontier.sort();
+        expected.sort();
+        if frontier != expected {
+            panic!("Output {} has frontier {:?}, expected {:?}", output, frontier, expected);
+        }
+    }
+
+    /// Asserts that `count` messages in total have been consumed at `time` on `input`.
+    pub fn assert_consumed(&mut self, input: usize, time: T, count: i64) {
+        let consumed = self.consumed[input].iter().filter(|(t, _)| t == &time).map(|(_, c)| *c).sum::<i64>();
+        if consumed != count {
+            panic!("Input {} consumed {} messages at {:?}, expected {}", input, consumed, time, count);
+        }
+    }
+
+    /// Asserts that `count` messages in total have been produced at `time` on `output`.
+    pub fn assert_produced(&mut self, output: usize, time: T, count: i64) {
+        let produced = self.produced[output].iter().filter(|(t, _)| t == &time).map(|(_, c)| *c).sum::<i64>();
+        if produced != count {
+            panic!("Output {} produced {} messages at {:?}, expected {}", output, produced, time, count);
+        }
+    }
+
+    /// Asserts that no capabilities are held for any output.
+    pub fn assert_quiescent(&self) {
+        for (output, capabilities) in self.capabilities.iter().enumerate() {
+            if !capabilities.is_empty() {
+                panic!("Output {} holds capabilities at {:?}, expected none", output, capabilities.frontier().to_vec());
+            }
+        }
+    }
+}
+
+/// Drives `operator` through advancing input frontiers, and reports violations of its contract.
+///
+/// The operator's input frontiers start at the minimal timestamp, advance independently through
+/// `times`, which should be increasing, and are then closed. The order in which the inputs advance,
+/// and the number of times the operator is scheduled in between, are chosen pseudo-randomly from
+/// `seed`. After each scheduling the progress reported by the operator is checked as follows:
+///
+/// * Consumed message counts must be non-negative.
+/// * Capabilities acquired and messages produced at a time must be justified, either by a held
+///   capability less or equal to the time, or by an input frontier element or a consumed message
+///   that the operator's internal summary advances to a time less or equal to it.
+/// * Held capability counts must remain non-negative.
+///
+/// As no messages are delivered to the operator, consumed messages reflect only what it reports.
+/// The result lists all violations observed, in the order they were observed.
+pub fn check_operate_contract<T, O>(operator: &mut O, times: &[T], seed: u64) -> Result<(), Vec<String>>
+where
+    T: Timestamp,
+    O: Operate<T>+?Sized,
+{
+    let mut violations = Vec::new();
+
+    let inputs = operator.inputs();
+    let outputs = operator.outputs();
+    let (summary, shared) = operator.get_internal_summary();
+
+    if summary.len() != inputs || summary.iter().any(|x| x.len() != outputs) {
+        violations.push(format!("Internal summary does not have shape {} x {}", inputs, outputs));
+        return Err(violations);
+    }
+    {
+        let shared = shared.borrow();
+        if shared.frontiers.len() != inputs || shared.consumeds.len() != inputs ||
+           shared.internals.len() != outputs || shared.produceds.len() != outputs {
+            violations.push(format!("Shared progress does not have {} inputs and {} outputs", inputs, outputs));
+            return Err(violations);
+        }
+    }
+
+    // Initial capabilities may be at any time.
+    let mut held = vec![ChangeBatch::new(); outputs];
+    for (output, internal) in shared.borrow_mut().internals.iter_mut().enumerate() {
+        held[output].extend(internal.drain());
+    }
+    check_counts(&mut held, "initially", &mut violations);
+
+    // Each input starts at the minimal timestamp, and is advanced through `times` then closed.
+    let mut frontiers: Vec<Option<T>> = vec![Some(Default::default()); inputs];
+    let mut cursors = vec![0; inputs];
+    for frontier in shared.borrow_mut().frontiers.iter_mut() {
+        frontier.update(Default::default(), 1);
+    }
+    operator.set_external_summary();
+
+    let mut state = seed ^ 0x9E37_79B9_7F4A_7C15;
+    let mut random = move |bound: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % (bound as u64)) as usize
+    };
+
+    let mut round = 0;
+    loop {
+
+        for _ in 0 .. 1 + random(3) {
+            let frontier =
+            frontiers
+                .iter()
+                .enumerate()
+                .flat_map(|(input, time)| time.iter().map(move |time| (input, time.clone())))
+                .collect::<Vec<_>>();
+            let mut consumed = Vec::new();
+            let mut produced = vec![ChangeBatch::new(); outputs];
+            let mut acquired = vec![ChangeBatch::new(); outputs];
+
+            operator.schedule();
+
+            let mut shared = shared.borrow_mut();
+            for (input, consumeds) in shared.consumeds.iter_mut().enumerate() {
+                for (time, count) in consumeds.drain() {
+                    if count < 0 {
+                        violations.push(format!("Round {}: consumed {} messages at {:?} on input {}", round, count, time, input));
+                    }
+                    consumed.push((input, time));
+                }
+            }
+            for output in 0 .. outputs {
+                acquired[output].extend(shared.internals[output].drain());
+                produced[output].extend(shared.produceds[output].drain());
+            }
+            drop(shared);
+
+            let justified = |held: &mut ChangeBatch<T>, output: usize, time: &T| {
+                held.iter().any(|(t, count)| *count > 0 && t.less_equal(time)) ||
+                summary_justifies(&summary, output, time, frontier.iter().chain(consumed.iter()).map(|(input, t)| (*input, t)))
+            };
+
+            for output in 0 .. outputs {
+                for (time, count) in acquired[output].iter() {
+                    if *count > 0 && !justified(&mut held[output], output, time) {
+                        violations.push(format!("Round {}: unjustified capability at {:?} on output {}", round, time, output));
+                    }
+                }
+                let before = held[output].clone();
+                held[output].extend(acquired[output].drain());
+                let mut during = before;
+                during.extend(held[output].iter().cloned());
+                for (time, count) in produced[output].iter() {
+                    if *count < 0 {
+                        violations.push(format!("Round {}: produced {} messages at {:?} on output {}", round, count, time, output));
+                    }
+                    else if !justified(&mut during, output, time) {
+                        violations.push(format!("Round {}: unjustified message at {:?} on output {}", round, time, output));
+                    }
+                }
+            }
+            check_counts(&mut held, &format!("in round {}", round), &mut violations);
+            round += 1;
+        }
+
+        // Advance or close a randomly chosen open input, or stop once all are closed.
+        let open = (0 .. inputs).filter(|i| frontiers[*i].is_some()).collect::<Vec<_>>();
+        if open.is_empty() { break; }
+        let input = open[random(open.len())];
+        let next = times.get(cursors[input]).cloned();
+        cursors[input] += 1;
+        let mut shared = shared.borrow_mut();
+        if let Some(time) = frontiers[input].take() {
+            shared.frontiers[input].update(time, -1);
+        }
+        if let Some(time) = next.clone() {
+            shared.frontiers[input].update(time, 1);
+        }
+        frontiers[input] = next;
+    }
+
+    if violations.is_empty() { Ok(()) } else { Err(violations) }
+}
+
+/// Records a violation for each held capability count that is negative.
+fn check_counts<T: Timestamp>(held: &mut [ChangeBatch<T>], when: &str, violations: &mut Vec<String>) {
+    for (output, held) in held.iter_mut().enumerate() {
+        for (time, count) in held.iter() {
+            if *count < 0 {
+                violations.push(format!("Capability count {} at {:?} on output {} {}", count, time, output, when));
+            }
+        }
+    }
+}
@@ -82,6 +82,80 @@ macro_rules! implement_timestamp_add {
 
 implement_timestamp_add!(usize, u128, u64, u32, u16, u8, isize, i128, i64, i32, i16, i8, ::std::time::Duration,);
 
+/// A count of loop iterations, whose summaries may bound the number of iterations.
+///
+/// An `Iteration` is ordered as the count it wraps, but is summarized by `BoundedLoop`, which
+/// allows a loop's feedback edge to stop producing times after a fixed number of iterations.
+#[derive(Abomonation, Copy, Clone, Hash, Eq, PartialEq, Default, Ord, PartialOrd, Debug, Serialize, Deserialize)]
+pub struct Iteration(pub usize);
+
+impl PartialOrder for Iteration {
+    #[inline] fn less_equal(&self, other: &Self) -> bool { self.0 <= other.0 }
+}
+impl crate::order::TotalOrder for Iteration { }
+
+impl Timestamp for Iteration { type Summary = BoundedLoop; }
+
+/// A summary advancing an `Iteration` by `increment`, producing no time beyond `cap`.
+///
+/// The default summary advances by zero without a cap, and is the identity.
+///
+/// # Examples
+/// ```
+/// use timely::progress::PathSummary;
+/// use timely::progress::timestamp::{BoundedLoop, Iteration};
+///
+/// let feedback = BoundedLoop { increment: 1, cap: Some(10) };
+/// assert_eq!(feedback.results_in(&Iteration(9)), Some(Iteration(10)));
+/// assert_eq!(feedback.results_in(&Iteration(10)), None);
+///
+/// // Following one summary by another must respect both caps.
+/// let twice = feedback.followed_by(&feedback).unwrap();
+/// assert_eq!(twice, BoundedLoop { increment: 2, cap: Some(10) });
+/// assert_eq!(twice.results_in(&Iteration(8)), Some(Iteration(10)));
+/// assert_eq!(twice.results_in(&Iteration(9)), None);
+/// ```
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Default, Debug)]
+pub struct BoundedLoop {
+    /// The amount by which the iteration count advances.
+    pub increment: usize,
+    /// The greatest iteration count produced, if any.
+    pub cap: Option<usize>,
+}
+
+impl PartialOrder for BoundedLoop {
+    /// A summary is less or equal to another if it advances times no further, and produces times
+    /// for at least the same inputs.
+    #[inline]
+    fn less_equal(&self, other: &Self) -> bool {
+        self.increment <= other.increment &&
+        match (self.cap, other.cap) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(cap1), Some(cap2)) => cap1 >= cap2,
+        }
+    }
+}
+
+impl PathSummary<Iteration> for BoundedLoop {
+    #[inline]
+    fn results_in(&self, src: &Iteration) -> Option<Iteration> {
+        src.0.checked_add(self.increment)
+            .filter(|count| self.cap.map(|cap| *count <= cap).unwrap_or(true))
+            .map(Iteration)
+    }
+    #[inline]
+    fn followed_by(&self, other: &BoundedLoop) -> Option<BoundedLoop> {
+        // The first cap applies before the second increment, after which it is that much greater.
+        let increment = self.increment.checked_add(other.increment)?;
+        let cap = match (self.cap.map(|cap| cap.saturating_add(other.increment)), other.cap) {
+            (Some(cap1), Some(cap2)) => Some(::std::cmp::min(cap1, cap2)),
+            (cap1, cap2) => cap1.or(cap2),
+        };
+        Some(BoundedLoop { increment, cap })
+    }
+}
+
 pub use self::refines::Refines;
 mod refines {
 
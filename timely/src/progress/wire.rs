@@ -0,0 +1,107 @@
+//! A compact binary encoding of progress updates.
+//!
+//! Progress updates are presented as one list of `(time, diff)` updates per port, as found in
+//! the `consumeds`, `internals`, and `produceds` fields of `SharedProgress`. The encoding is
+//! meant for integer timestamps, and records each port's updates in order, with each time as
+//! the difference from the preceding time on the same port. All integers are encoded as
+//! variable-length integers, with signed integers zig-zag encoded, so that the small numbers
+//! typical of progress updates occupy few bytes.
+//!
+//! # Examples
+//!
+//! ```
+//! use timely::progress::wire::{encode_progress, decode_progress};
+//!
+//! let progress: Vec<Vec<(u64, i64)>> = vec![
+//!     vec![(5, 1), (3, -1), (1_000_000, 2)],
+//!     vec![],
+//!     vec![(0, -7), (u64::max_value(), 1)],
+//! ];
+//!
+//! let bytes = encode_progress(&progress);
+//! assert_eq!(decode_progress::<u64>(&bytes), Some(progress));
+//!
+//! // Truncated input, and timestamps out of range, are not decoded.
+//! assert_eq!(decode_progress::<u64>(&bytes[.. bytes.len() - 1]), None);
+//! assert_eq!(decode_progress::<u32>(&bytes), None);
+//!
+//! // Signed timestamps may be negative.
+//! let progress: Vec<Vec<(i64, i64)>> = vec![vec![(-3, 1), (i64::min_value(), -1), (i64::max_value(), 1)]];
+//! let bytes = encode_progress(&progress);
+//! assert_eq!(decode_progress::<i64>(&bytes), Some(progress));
+//! assert_eq!(decode_progress::<u64>(&bytes), None);
+//! ```
+
+use std::convert::{TryFrom, TryInto};
+
+/// Encodes lists of progress updates, one for each port.
+///
+/// Timestamps are encoded as `i128` values, and this method panics if a timestamp cannot be
+/// represented as one, which among the primitive integers is only the case for the greatest
+/// `u128` values.
+pub fn encode_progress<T: Clone+TryInto<i128>>(progress: &[Vec<(T, i64)>]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    encode_varint(progress.len() as u128, &mut bytes);
+    for updates in progress.iter() {
+        encode_varint(updates.len() as u128, &mut bytes);
+        let mut previous = 0i128;
+        for (time, diff) in updates.iter() {
+            let time = time.clone().try_into().ok().expect("Timestamp exceeds encoding range.");
+            encode_varint(zigzag(time.wrapping_sub(previous)), &mut bytes);
+            encode_varint(zigzag(i128::from(*diff)), &mut bytes);
+            previous = time;
+        }
+    }
+    bytes
+}
+
+/// Decodes lists of progress updates, one for each port.
+///
+/// The result is `None` if `bytes` is not exactly the encoding of progress updates whose
+/// timestamps can be represented by `T`.
+pub fn decode_progress<T: TryFrom<i128>>(mut bytes: &[u8]) -> Option<Vec<Vec<(T, i64)>>> {
+    let ports = decode_varint(&mut bytes)?;
+    let mut progress = Vec::new();
+    for _ in 0 .. ports {
+        let count = decode_varint(&mut bytes)?;
+        let mut updates = Vec::new();
+        let mut previous = 0i128;
+        for _ in 0 .. count {
+            let time = previous.wrapping_add(unzigzag(decode_varint(&mut bytes)?));
+            let diff = i64::try_from(unzigzag(decode_varint(&mut bytes)?)).ok()?;
+            updates.push((T::try_from(time).ok()?, diff));
+            previous = time;
+        }
+        progress.push(updates);
+    }
+    if bytes.is_empty() { Some(progress) } else { None }
+}
+
+/// Appends `value` to `bytes`, seven bits at a time, least significant first.
+fn encode_varint(mut value: u128, bytes: &mut Vec<u8>) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Reads a value from the front of `bytes`, advancing `bytes` past it.
+fn decode_varint(bytes: &mut &[u8]) -> Option<u128> {
+    let mut value = 0u128;
+    let mut shift = 0;
+    loop {
+        let (byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        if shift > 126 || (shift == 126 && *byte > 3) { return None; }
+        value |= u128::from(byte & 0x7F) << shift;
+        if byte & 0x80 == 0 { return Some(value); }
+        shift += 7;
+    }
+}
+
+/// Maps signed integers to unsigned integers, so that values near zero are small.
+fn zigzag(value: i128) -> u128 { ((value << 1) ^ (value >> 127)) as u128 }
+
+/// Inverts `zigzag`.
+fn unzigzag(value: u128) -> i128 { ((value >> 1) as i128) ^ -((value & 1) as i128) }
@@ -8,6 +8,8 @@ use std::collections::BinaryHeap;
 use std::time::{Duration, Instant};
 use std::cmp::Reverse;
 
+use crate::scheduling::clock::Clock;
+
 /// Allocation-free activation tracker.
 pub struct Activations {
     clean: usize,
@@ -21,7 +23,7 @@ pub struct Activations {
     rx: Receiver<Vec<usize>>,
 
     // Delayed activations.
-    timer: Instant,
+    timer: Box<dyn Clock>,
     queue: BinaryHeap<Reverse<(Duration, Vec<usize>)>>,
 }
 
@@ -37,11 +39,51 @@ impl Activations {
             buffer: Vec::new(),
             tx,
             rx,
-            timer,
+            timer: Box::new(timer),
             queue: BinaryHeap::new(),
         }
     }
 
+    /// Replaces the clock used to time delayed activations.
+    ///
+    /// Delayed activations are scheduled relative to the time elapsed on the clock, and the clock
+    /// should be replaced before any are scheduled. A `ManualClock` allows delayed activations to
+    /// be tested deterministically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::RefCell;
+    /// use std::time::{Duration, Instant};
+    /// use timely::scheduling::Activations;
+    /// use timely::scheduling::clock::ManualClock;
+    ///
+    /// let clock = ManualClock::new();
+    /// let mut activations = Activations::new(Instant::now());
+    /// activations.set_clock(Box::new(clock.clone()));
+    ///
+    /// // A watchdog that should fire after five seconds.
+    /// activations.activate_after(&[1], Duration::from_secs(5));
+    ///
+    /// let active = |activations: &Activations| {
+    ///     let paths = RefCell::new(Vec::new());
+    ///     activations.map_active(|path| paths.borrow_mut().push(path.to_vec()));
+    ///     paths.into_inner()
+    /// };
+    ///
+    /// clock.advance(Duration::from_secs(4));
+    /// activations.advance();
+    /// assert!(active(&activations).is_empty());
+    /// assert_eq!(activations.empty_for(), Some(Duration::from_secs(1)));
+    ///
+    /// clock.advance(Duration::from_secs(1));
+    /// activations.advance();
+    /// assert_eq!(active(&activations), vec![vec![1]]);
+    /// ```
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.timer = clock;
+    }
+
     /// Activates the task addressed by `path`.
     pub fn activate(&mut self, path: &[usize]) {
         self.bounds.push((self.slices.len(), path.len()));
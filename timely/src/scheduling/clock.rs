@@ -0,0 +1,49 @@
+//! Sources of time for timing-dependent scheduling.
+
+use std::rc::Rc;
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// A source of elapsed time.
+pub trait Clock {
+    /// The time elapsed since the clock's origin.
+    fn elapsed(&self) -> Duration;
+}
+
+impl Clock for Instant {
+    fn elapsed(&self) -> Duration { Instant::elapsed(self) }
+}
+
+/// A clock that advances only when instructed to.
+///
+/// A manual clock allows tests of timing-dependent behavior, for example delayed activations,
+/// to proceed deterministically. Clones of a manual clock share the same elapsed time.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use timely::scheduling::clock::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// let shared = clock.clone();
+/// clock.advance(Duration::from_secs(3));
+/// assert_eq!(shared.elapsed(), Duration::from_secs(3));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ManualClock {
+    elapsed: Rc<Cell<Duration>>,
+}
+
+impl ManualClock {
+    /// Creates a new manual clock, with no time elapsed.
+    pub fn new() -> Self { Self::default() }
+    /// Advances the clock by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed.set(self.elapsed.get() + duration);
+    }
+}
+
+impl Clock for ManualClock {
+    fn elapsed(&self) -> Duration { self.elapsed.get() }
+}
@@ -4,6 +4,7 @@ use std::rc::Rc;
 use std::cell::RefCell;
 
 pub mod activate;
+pub mod clock;
 
 pub use self::activate::{Activations, Activator, ActivateOnDrop, SyncActivator};
 
@@ -0,0 +1,48 @@
+extern crate timely;
+
+use std::sync::{Arc, Mutex};
+
+use timely::dataflow::{InputHandle, ProbeHandle, Scope};
+use timely::dataflow::operators::{Input, Concat, ConnectLoop, Enter, Inspect, Leave, LoopVariable, Probe};
+use timely::progress::timestamp::{BoundedLoop, Iteration};
+
+// This test asserts that a loop whose feedback edge is capped at ten iterations circulates each
+// record through the iterations up to the cap, after which the frontier downstream of it empties.
+#[test]
+fn bounded_loop_terminates() {
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen2 = seen.clone();
+
+    timely::execute_directly(move |worker| {
+
+        let mut input = InputHandle::new();
+        let mut probe = ProbeHandle::new();
+        worker.dataflow::<u64,_,_>(|scope| {
+            let stream = scope.input_from(&mut input);
+            scope.iterative::<Iteration,_,_>(|inner| {
+                let (handle, cycle) = inner.loop_variable(BoundedLoop { increment: 1, cap: Some(10) });
+                let seen2 = seen2.clone();
+                let results = stream
+                    .enter(inner)
+                    .concat(&cycle)
+                    .inspect_time(move |time, _| seen2.lock().unwrap().push(time.inner.0));
+                results.connect_loop(handle);
+                results.leave()
+            })
+            .probe_with(&mut probe);
+        });
+
+        input.send(0u64);
+        input.close();
+        let mut steps = 0;
+        while !probe.done() {
+            assert!(steps < 1000, "bounded loop failed to terminate");
+            worker.step();
+            steps += 1;
+        }
+    });
+
+    let seen = seen.lock().unwrap().clone();
+    assert_eq!(seen, (0 .. 11).collect::<Vec<_>>());
+}
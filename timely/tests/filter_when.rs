@@ -0,0 +1,39 @@
+extern crate timely;
+
+use std::sync::{Arc, Mutex};
+
+use timely::dataflow::{InputHandle, ProbeHandle};
+use timely::dataflow::operators::{Input, FilterWhen, Inspect, Probe};
+
+// This test asserts that filtering by time forwards only records at the selected times, and that
+// the frontier downstream of the filter advances past the times filtered out, as they complete.
+#[test]
+fn filter_when_advances_frontier() {
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen2 = seen.clone();
+
+    timely::execute_directly(move |worker| {
+
+        let mut input = InputHandle::new();
+        let mut probe = ProbeHandle::new();
+        worker.dataflow::<u64,_,_>(|scope| {
+            scope
+                .input_from(&mut input)
+                .filter_when(|time| time % 2 == 0)
+                .inspect_batch(move |time, data| seen2.lock().unwrap().extend(data.iter().map(|x| (*time, *x))))
+                .probe_with(&mut probe);
+        });
+
+        for round in 0 .. 10u64 {
+            input.send(round);
+            input.advance_to(round + 1);
+            worker.step_while(|| probe.less_than(input.time()));
+            assert!(!probe.less_than(&(round + 1)));
+        }
+    });
+
+    let mut seen = seen.lock().unwrap().clone();
+    seen.sort();
+    assert_eq!(seen, vec![(0, 0), (2, 2), (4, 4), (6, 6), (8, 8)]);
+}
@@ -0,0 +1,73 @@
+extern crate timely;
+
+use std::sync::{Arc, Mutex};
+
+use timely::Configuration;
+use timely::dataflow::Scope;
+use timely::dataflow::operators::{Concat, ConnectLoop, Filter, Inspect, Leave, LoopVariable, Map};
+use timely::dataflow::operators::generic::operator::source;
+use timely::order::Product;
+use timely::scheduling::Scheduler;
+
+const BOUND: u64 = 10;
+
+#[test] fn loop_generator_1w() { loop_generator_helper(Configuration::Thread, 1); }
+#[test] fn loop_generator_2w() { loop_generator_helper(Configuration::Process(2), 2); }
+
+// This method asserts that a zero-input generator inside a loop emits exactly once per iteration,
+// and that the loop's scope, which has no inputs of its own, completes once the generator stops.
+fn loop_generator_helper(config: ::timely::Configuration, peers: u64) {
+
+    let emitted = Arc::new(Mutex::new(Vec::new()));
+    let departed = Arc::new(Mutex::new(0));
+
+    let emitted2 = emitted.clone();
+    let departed2 = departed.clone();
+
+    timely::execute(config, move |worker| {
+        let emitted = emitted2.clone();
+        let departed = departed2.clone();
+        worker.dataflow::<u64,_,_>(move |scope| {
+            scope.iterative::<u64,_,_>(|inner| {
+
+                let (handle, cycle) = inner.loop_variable(1);
+
+                // emits its iteration number once per iteration, up to `BOUND`.
+                let generated = source(inner, "Generator", |capability, info| {
+                    let activator = inner.activator_for(&info.address[..]);
+                    let mut cap = Some(capability);
+                    move |output| {
+                        if let Some(mut capability) = cap.take() {
+                            let round = capability.time().inner;
+                            output.session(&capability).give(round);
+                            if round + 1 < BOUND {
+                                capability.downgrade(&Product::new(0, round + 1));
+                                cap = Some(capability);
+                                activator.activate();
+                            }
+                        }
+                    }
+                });
+
+                generated
+                    .inspect_time(move |time, round| {
+                        assert_eq!(time.inner, *round);
+                        emitted.lock().unwrap().push(*round);
+                    })
+                    .concat(&cycle)
+                    .map(|round| round + 1)
+                    .filter(|round| *round < BOUND)
+                    .connect_loop(handle);
+
+                generated.leave()
+            })
+            .inspect(move |_| *departed.lock().unwrap() += 1);
+        });
+    }).unwrap(); // asserts error-free execution.
+
+    let mut emitted = emitted.lock().unwrap().clone();
+    emitted.sort();
+    let expected = (0 .. BOUND).flat_map(|round| (0 .. peers).map(move |_| round)).collect::<Vec<_>>();
+    assert_eq!(emitted, expected);
+    assert_eq!(*departed.lock().unwrap(), peers * BOUND);
+}
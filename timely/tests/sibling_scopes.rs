@@ -0,0 +1,56 @@
+extern crate timely;
+
+use std::sync::{Arc, Mutex};
+
+use timely::dataflow::Scope;
+use timely::dataflow::operators::{ConnectLoop, Concat, Enter, Filter, Inspect, Leave, LoopVariable, Map, ToStream};
+use timely::order::Product;
+
+// This test asserts that sibling scopes may refine the same parent timestamp with different inner
+// timestamps. Each scope presents itself to the parent as an operator on the parent's timestamp,
+// and so only the outer projection of its timestamps must agree with its siblings.
+#[test]
+fn sibling_scopes_distinct_inner() {
+
+    let counted = Arc::new(Mutex::new(Vec::new()));
+    let producted = Arc::new(Mutex::new(Vec::new()));
+
+    let counted2 = counted.clone();
+    let producted2 = producted.clone();
+
+    timely::example(move |scope| {
+
+        let values = (1 .. 6u64).to_stream(scope);
+
+        // Counts down each value in a loop with a `u32` iteration counter.
+        let counted =
+        scope.iterative::<u32,_,_>(|inner| {
+            let (handle, cycle) = inner.loop_variable(1);
+            let stream = values.enter(inner).concat(&cycle);
+            stream.map(|x| x - 1).filter(|x| *x > 0).connect_loop(handle);
+            stream.leave()
+        });
+
+        // Counts down each value in a loop with a product iteration counter.
+        let producted =
+        scope.iterative::<Product<u32, u32>,_,_>(|inner| {
+            let (handle, cycle) = inner.loop_variable(Product::new(0, 1));
+            let stream = values.enter(inner).concat(&cycle);
+            stream.map(|x| x - 1).filter(|x| *x > 0).connect_loop(handle);
+            stream.leave()
+        });
+
+        counted.inspect(move |x| counted2.lock().unwrap().push(*x));
+        producted.inspect(move |x| producted2.lock().unwrap().push(*x));
+    });
+
+    let mut counted = counted.lock().unwrap().clone();
+    let mut producted = producted.lock().unwrap().clone();
+    counted.sort();
+    producted.sort();
+
+    let mut expected = (1 .. 6u64).flat_map(|x| 1 ..= x).collect::<Vec<_>>();
+    expected.sort();
+    assert_eq!(counted, expected);
+    assert_eq!(producted, expected);
+}
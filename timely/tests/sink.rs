@@ -0,0 +1,51 @@
+extern crate timely;
+
+use std::sync::{Arc, Mutex};
+
+use timely::dataflow::InputHandle;
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::Input;
+use timely::dataflow::operators::generic::operator::Operator;
+
+// This test asserts that an operator without outputs consumes every record fed to it, and that
+// the dataflow completes once its input closes, as the sink holds no capabilities of its own.
+#[test]
+fn sink_consumes_and_quiesces() {
+
+    let consumed = Arc::new(Mutex::new(Vec::new()));
+    let closed = Arc::new(Mutex::new(false));
+
+    let consumed2 = consumed.clone();
+    let closed2 = closed.clone();
+
+    timely::execute_directly(move |worker| {
+
+        let mut input = InputHandle::new();
+        worker.dataflow::<u64,_,_>(|scope| {
+            scope
+                .input_from(&mut input)
+                .sink(Pipeline, "Sink", move |input| {
+                    while let Some((time, data)) = input.next() {
+                        consumed2.lock().unwrap().extend(data.iter().map(|x| (*time.time(), *x)));
+                    }
+                    if input.frontier().is_empty() {
+                        *closed2.lock().unwrap() = true;
+                    }
+                });
+        });
+
+        for round in 0 .. 10u64 {
+            input.advance_to(round);
+            for datum in 0 .. round {
+                input.send(datum);
+            }
+            worker.step();
+        }
+    });
+
+    let mut consumed = consumed.lock().unwrap().clone();
+    consumed.sort();
+    let expected = (0 .. 10u64).flat_map(|round| (0 .. round).map(move |datum| (round, datum))).collect::<Vec<_>>();
+    assert_eq!(consumed, expected);
+    assert!(*closed.lock().unwrap());
+}